@@ -0,0 +1,138 @@
+// On-disk HTTP response cache keyed by URL
+// Bodies are stored next to a small JSON metadata file holding the validators
+// (ETag / Last-Modified) used to make conditional requests on later runs.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Constants
+pub const DEFAULT_CACHE_DIR: &str = ".vivatech-cache";
+
+// Validators returned by the server for conditional requests
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Validators {
+    #[serde(default)]
+    pub etag: Option<String>,
+    #[serde(default)]
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    pub const fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CacheMetadata {
+    url: String,
+    // Seconds since the Unix epoch when the body was last fetched or revalidated
+    fetched_at: u64,
+    #[serde(flatten)]
+    validators: Validators,
+}
+
+// A cached response body with its validators
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub body: String,
+    pub validators: Validators,
+    pub fetched_at: SystemTime,
+}
+
+impl CachedResponse {
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.fetched_at)
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    max_age: Duration,
+}
+
+impl ResponseCache {
+    // Cache in `dir`; entries younger than `max_age` are reused without a request
+    pub fn new(dir: impl Into<PathBuf>, max_age: Duration) -> Self {
+        Self {
+            dir: dir.into(),
+            max_age,
+        }
+    }
+
+    pub const fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    // Base path (without extension) of the files caching `url`
+    fn entry_path(&self, url: &str) -> PathBuf {
+        let digest = Sha256::digest(url.as_bytes());
+        self.dir.join(format!("{digest:x}"))
+    }
+
+    // Look up the cached response for `url`
+    pub fn lookup(&self, url: &str) -> Option<CachedResponse> {
+        let path = self.entry_path(url);
+        let metadata: CacheMetadata = match fs::read_to_string(path.with_extension("json")) {
+            Ok(json) => match serde_json::from_str(&json) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    log::warn!("Ignoring corrupt cache metadata for {url}: {e}");
+                    return None;
+                }
+            },
+            Err(_) => return None,
+        };
+        if metadata.url != url {
+            return None;
+        }
+
+        let body = fs::read_to_string(path.with_extension("body")).ok()?;
+        log::debug!("Found cached response for {url}");
+        Some(CachedResponse {
+            body,
+            validators: metadata.validators,
+            fetched_at: UNIX_EPOCH + Duration::from_secs(metadata.fetched_at),
+        })
+    }
+
+    // Store a freshly fetched body
+    pub fn store(&self, url: &str, validators: &Validators, body: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache directory: {}", self.dir.display()))?;
+
+        let path = self.entry_path(url);
+        fs::write(path.with_extension("body"), body)
+            .with_context(|| format!("Failed to write cached body for {url}"))?;
+        self.write_metadata(url, validators)?;
+
+        log::info!("Cached {} bytes for {url}", body.len());
+        Ok(())
+    }
+
+    // Mark the cached body as revalidated now (after a 304 Not Modified)
+    pub fn refresh(&self, url: &str, validators: &Validators) -> Result<()> {
+        self.write_metadata(url, validators)
+    }
+
+    fn write_metadata(&self, url: &str, validators: &Validators) -> Result<()> {
+        let metadata = CacheMetadata {
+            url: url.to_string(),
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or(Duration::ZERO)
+                .as_secs(),
+            validators: validators.clone(),
+        };
+        let json = serde_json::to_string_pretty(&metadata)?;
+        fs::write(self.entry_path(url).with_extension("json"), json)
+            .with_context(|| format!("Failed to write cache metadata for {url}"))
+    }
+}
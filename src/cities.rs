@@ -0,0 +1,149 @@
+// Offline city gazetteer for VivaTech
+// Resolves city names to their country and region using an embedded table of
+// world cities ranked by population. A name shared by several places resolves
+// to one of them only when it is far larger, and the homonyms it won over are
+// reported with it; otherwise the ambiguity is reported.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use crate::countries::{self, normalize};
+
+// Tab-separated rows: alpha-2, name, `;`-separated region names, population,
+// `|`-separated alternate names
+const CITY_TABLE: &str = include_str!("../data/cities.tsv");
+
+// How many times larger the most populous homonym must be to win outright
+const DOMINANCE_RATIO: u64 = 10;
+
+// A city of the gazetteer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct City {
+    pub name: &'static str,
+    // ISO 3166-1 alpha-2 code of the country
+    pub country: &'static str,
+    pub region: &'static str,
+    pub population: u64,
+    region_aliases: &'static str,
+}
+
+impl City {
+    // Check whether `qualifier` names this city's region (e.g. "Texas" or "TX")
+    fn in_region(&self, qualifier: &str) -> bool {
+        let qualifier = normalize(qualifier);
+        std::iter::once(self.region)
+            .chain(self.region_aliases.split(';'))
+            .any(|region| !region.is_empty() && normalize(region) == qualifier)
+    }
+
+    // Check whether `qualifier` names this city's country
+    fn in_country(&self, qualifier: &str) -> bool {
+        countries::resolve(qualifier).is_some_and(|country| country.alpha2 == self.country)
+    }
+}
+
+// Outcome of resolving a city name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CityResolution {
+    Found(City),
+    // Several places match and `city` is far larger than the others, which
+    // follow most populous first
+    Dominant { city: City, homonyms: Vec<City> },
+    // Several places match and none clearly dominates, most populous first
+    Ambiguous(Vec<City>),
+    Unknown,
+}
+
+struct CityIndex {
+    cities: Vec<City>,
+    // Normalized name or alternate name to positions in `cities`
+    by_name: HashMap<String, Vec<usize>>,
+    // Normalized names of every known region
+    regions: HashSet<String>,
+}
+
+fn index() -> &'static CityIndex {
+    static INDEX: OnceLock<CityIndex> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        let mut cities = Vec::new();
+        let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut known_regions = HashSet::new();
+
+        for line in CITY_TABLE
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let fields: Vec<&'static str> = line.split('\t').collect();
+            let [country, name, regions, population, alternates] = fields[..] else {
+                continue;
+            };
+            let (region, region_aliases) = regions.split_once(';').unwrap_or((regions, ""));
+            known_regions.extend(regions.split(';').map(normalize));
+
+            let position = cities.len();
+            cities.push(City {
+                name,
+                country,
+                region,
+                population: population.parse().unwrap_or(0),
+                region_aliases,
+            });
+            for alias in std::iter::once(name).chain(alternates.split('|')) {
+                let key = normalize(alias);
+                if key.is_empty() {
+                    continue;
+                }
+                let positions = by_name.entry(key).or_default();
+                if !positions.contains(&position) {
+                    positions.push(position);
+                }
+            }
+        }
+
+        CityIndex {
+            cities,
+            by_name,
+            regions: known_regions,
+        }
+    })
+}
+
+// Every city named `name`, most populous first
+pub fn lookup(name: &str) -> Vec<City> {
+    let index = index();
+    let mut cities: Vec<City> = index
+        .by_name
+        .get(&normalize(name))
+        .map(|positions| positions.iter().map(|&p| index.cities[p]).collect())
+        .unwrap_or_default();
+    cities.sort_by_key(|city| std::cmp::Reverse(city.population));
+    cities
+}
+
+// Check whether `qualifier` names a known region or country
+fn is_place_qualifier(qualifier: &str) -> bool {
+    index().regions.contains(&normalize(qualifier)) || countries::resolve(qualifier).is_some()
+}
+
+// Resolve a city given as "City", "City, Region" or "City, Region, Country"
+// Qualifiers that name no known place, such as postcodes, are ignored.
+pub fn resolve(text: &str) -> CityResolution {
+    let mut parts = text.split(',').map(str::trim);
+    let mut candidates = lookup(parts.next().unwrap_or_default());
+
+    for qualifier in parts.filter(|part| is_place_qualifier(part)) {
+        candidates.retain(|city| city.in_region(qualifier) || city.in_country(qualifier));
+    }
+
+    match candidates.as_slice() {
+        [] => CityResolution::Unknown,
+        [city] => CityResolution::Found(*city),
+        [first, second, ..] if first.population >= second.population * DOMINANCE_RATIO => {
+            CityResolution::Dominant {
+                city: *first,
+                homonyms: candidates[1..].to_vec(),
+            }
+        }
+        _ => CityResolution::Ambiguous(candidates),
+    }
+}
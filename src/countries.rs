@@ -0,0 +1,132 @@
+// ISO 3166 country resolution for VivaTech
+// Countries are looked up in an embedded ISO 3166-1 table listing each
+// country's codes, display name, aliases and native names.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+// Tab-separated rows: alpha-2, alpha-3, display name, `|`-separated aliases
+const COUNTRY_TABLE: &str = include_str!("../data/iso3166.tsv");
+
+// Shortest alias matched inside free text, so "us" or "UK" in a sentence
+// are not mistaken for countries
+const MIN_TEXT_MATCH_LEN: usize = 4;
+
+// An ISO 3166-1 country
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Country {
+    pub alpha2: &'static str,
+    pub alpha3: &'static str,
+    pub name: &'static str,
+}
+
+struct CountryIndex {
+    countries: Vec<Country>,
+    // Normalized name or alias to position in `countries`
+    by_name: HashMap<String, usize>,
+    // Word count of the longest normalized name
+    max_words: usize,
+}
+
+fn index() -> &'static CountryIndex {
+    static INDEX: OnceLock<CountryIndex> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        let mut countries = Vec::new();
+        let mut by_name = HashMap::new();
+
+        for line in COUNTRY_TABLE
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let mut fields = line.split('\t');
+            let (Some(alpha2), Some(alpha3), Some(name)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            let aliases = fields.next().unwrap_or_default();
+
+            let position = countries.len();
+            countries.push(Country {
+                alpha2,
+                alpha3,
+                name,
+            });
+            for alias in std::iter::once(name).chain(aliases.split('|')) {
+                let key = normalize(alias);
+                if !key.is_empty() {
+                    by_name.entry(key).or_insert(position);
+                }
+            }
+        }
+
+        let max_words = by_name
+            .keys()
+            .map(|key| key.split(' ').count())
+            .max()
+            .unwrap_or(1);
+        CountryIndex {
+            countries,
+            by_name,
+            max_words,
+        }
+    })
+}
+
+// Lowercase `text` and collapse punctuation and whitespace into single spaces
+pub fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Every country of the table
+pub fn all() -> &'static [Country] {
+    &index().countries
+}
+
+// Country with the given alpha-2 or alpha-3 code, in any case
+pub fn by_code(code: &str) -> Option<Country> {
+    let code = code.trim();
+    all().iter().copied().find(|country| {
+        country.alpha2.eq_ignore_ascii_case(code) || country.alpha3.eq_ignore_ascii_case(code)
+    })
+}
+
+// Country named exactly by `text`: a code, English name, alias or native name
+pub fn resolve(text: &str) -> Option<Country> {
+    let text = text.trim();
+    if text.len() <= 3 && text.chars().all(|c| c.is_ascii_uppercase()) {
+        if let Some(country) = by_code(text) {
+            return Some(country);
+        }
+    }
+
+    let index = index();
+    index
+        .by_name
+        .get(&normalize(text))
+        .map(|&position| index.countries[position])
+}
+
+// Country whose name or alias appears as whole words in `text`
+// The longest match wins, so "Guinea-Bissau" is preferred over "Guinea".
+pub fn find_in_text(text: &str) -> Option<Country> {
+    let words: Vec<String> = normalize(text).split(' ').map(str::to_string).collect();
+    let index = index();
+
+    for len in (1..=words.len().min(index.max_words)).rev() {
+        for window in words.windows(len) {
+            let candidate = window.join(" ");
+            if candidate.chars().count() < MIN_TEXT_MATCH_LEN {
+                continue;
+            }
+            if let Some(&position) = index.by_name.get(&candidate) {
+                return Some(index.countries[position]);
+            }
+        }
+    }
+    None
+}
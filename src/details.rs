@@ -0,0 +1,201 @@
+// Detail page crawling shared by the speaker and partner targets
+// Detail pages are fetched concurrently through the scraper, so retries and the
+// response cache apply to every page.
+
+use anyhow::Result;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+
+use crate::scraper::Scraper;
+
+// Constants
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+// Social profile links found on a detail page
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SocialLinks {
+    pub linkedin: Option<String>,
+    pub twitter: Option<String>,
+}
+
+impl SocialLinks {
+    // Record a profile URL if it points to a known network
+    fn add_url(&mut self, url: &str) {
+        let lower = url.to_ascii_lowercase();
+        if !lower.starts_with("http") || lower.contains("vivatech") {
+            return;
+        }
+        if lower.contains("linkedin.com/") {
+            self.linkedin.get_or_insert_with(|| url.to_string());
+        } else if lower.contains("twitter.com/")
+            || lower.contains("://x.com/")
+            || lower.contains(".x.com/")
+        {
+            self.twitter.get_or_insert_with(|| url.to_string());
+        }
+    }
+}
+
+// URL of the detail page of an item listed on `base_url`
+pub fn detail_url(base_url: &str, id: &str) -> String {
+    format!("{}/{id}", base_url.trim_end_matches('/'))
+}
+
+// Path of a companion file written next to `output_path`
+// e.g. `speakers.csv` with suffix `details` becomes `speakers_details.csv`.
+pub fn companion_path(output_path: &Path, suffix: &str) -> PathBuf {
+    let stem = output_path
+        .file_stem()
+        .map_or_else(|| "output".into(), |stem| stem.to_string_lossy());
+    let file_name = match output_path.extension() {
+        Some(ext) => format!("{stem}_{suffix}.{}", ext.to_string_lossy()),
+        None => format!("{stem}_{suffix}"),
+    };
+    output_path.with_file_name(file_name)
+}
+
+// Fetch `(id, url)` pages with at most `concurrency` requests in flight
+// Results are returned in input order, paired with their ID.
+pub fn crawl<T, F>(
+    scraper: &Scraper,
+    pages: &[(String, String)],
+    concurrency: usize,
+    extract: F,
+) -> Vec<(String, Result<T>)>
+where
+    T: Send,
+    F: Fn(&str, &str) -> Result<T> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let workers = concurrency.clamp(1, pages.len().max(1));
+
+    let mut results: Vec<(usize, Result<T>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((id, url)) = pages.get(index) else {
+                            break;
+                        };
+                        log::debug!("Fetching detail page {url}");
+                        let result = scraper
+                            .fetch_page_content(url)
+                            .and_then(|html| extract(&html, id));
+                        done.push((index, result));
+                    }
+                    done
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("detail crawl worker panicked"))
+            .collect()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results
+        .into_iter()
+        .map(|(index, result)| (pages[index].0.clone(), result))
+        .collect()
+}
+
+// Keys holding an item's own profiles, as a list of entries or a network-to-URL map
+const SOCIAL_KEYS: [&str; 6] = [
+    "socialNetworks",
+    "social_networks",
+    "socials",
+    "socialLinks",
+    "social_links",
+    "links",
+];
+
+// Find LinkedIn and X/Twitter profile links in the structured fields of an item
+// Only the item's own fields and social network entries are read: links in the
+// page markup or in nested objects (company, sessions, co-speakers, team members)
+// belong to someone else.
+pub fn find_social_links(objects: &[&Map<String, Value>]) -> SocialLinks {
+    let mut links = SocialLinks::default();
+    for obj in objects {
+        add_own_links(obj, &mut links);
+        for value in SOCIAL_KEYS.iter().filter_map(|key| obj.get(*key)) {
+            match value {
+                Value::Object(networks) => add_own_links(networks, &mut links),
+                Value::Array(entries) => {
+                    for entry in entries {
+                        match entry {
+                            Value::Object(entry) => add_own_links(entry, &mut links),
+                            Value::String(url) => links.add_url(url.trim()),
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    links
+}
+
+// Record the profile URLs held in the string fields of `obj` itself
+fn add_own_links(obj: &Map<String, Value>, links: &mut SocialLinks) {
+    // Entries like {"type": "twitter", "url": "@handle"} name their network
+    let network = ["type", "name", "network"]
+        .iter()
+        .find_map(|key| obj.get(*key).and_then(Value::as_str))
+        .map(str::to_ascii_lowercase);
+    let names_twitter = matches!(network.as_deref(), Some("twitter" | "x"));
+
+    for (key, value) in obj {
+        let Value::String(text) = value else {
+            continue;
+        };
+        let text = text.trim();
+        let key = key.to_ascii_lowercase();
+        let is_twitter_field = key.starts_with("twitter")
+            || key == "x"
+            || (names_twitter && matches!(key.as_str(), "url" | "link" | "value"));
+
+        if is_twitter_field && !text.is_empty() && !text.starts_with("http") {
+            links.add_url(&format!("https://x.com/{}", text.trim_start_matches('@')));
+        } else {
+            links.add_url(text);
+        }
+    }
+}
+
+// Convert rich-text HTML (as used for biographies) into plain text
+pub fn html_to_text(html: &str) -> String {
+    static BREAKS: OnceLock<Regex> = OnceLock::new();
+    static TAGS: OnceLock<Regex> = OnceLock::new();
+    static BLANK_LINES: OnceLock<Regex> = OnceLock::new();
+
+    let breaks = BREAKS.get_or_init(|| {
+        Regex::new(r"(?i)<br\s*/?>|</p>|</li>|</div>").expect("valid line break regex")
+    });
+    let tags = TAGS.get_or_init(|| Regex::new(r"<[^>]*>").expect("valid tag regex"));
+    let blank_lines =
+        BLANK_LINES.get_or_init(|| Regex::new(r"\n\s*\n+").expect("valid blank line regex"));
+
+    let text = breaks.replace_all(html, "\n");
+    let text = tags.replace_all(&text, "");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let text: Vec<&str> = text.lines().map(str::trim).collect();
+    blank_lines
+        .replace_all(&text.join("\n"), "\n\n")
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,525 @@
+// Snapshot comparison for VivaTech
+// Snapshots in any supported format are normalized to the CSV record columns,
+// so a CSV export can be compared with a JSON or SQLite one. Records are
+// matched by ID and compared field by field. Missing values compare equal
+// whether they were written as empty fields, "N/A" or a custom sentinel.
+
+use anyhow::{Context, Result};
+use csv::StringRecord;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::partners::{self, Partner};
+use crate::sessions::{self, Session};
+use crate::speakers::{self, Speaker};
+use crate::sqlite;
+
+// Placeholder older CSV exports wrote for missing values
+const LEGACY_NULL_VALUE: &str = "N/A";
+
+// Kind of records held by a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Entity {
+    Speakers,
+    Partners,
+    Sessions,
+}
+
+impl Entity {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Speakers => "speakers",
+            Self::Partners => "partners",
+            Self::Sessions => "sessions",
+        }
+    }
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Speakers => "Speakers",
+            Self::Partners => "Partners",
+            Self::Sessions => "Sessions",
+        }
+    }
+
+    // Recognize the entity from the CSV header
+    fn from_headers(headers: &StringRecord) -> Option<Self> {
+        if headers.iter().any(|header| header == "FirstName") {
+            Some(Self::Speakers)
+        } else if headers.iter().any(|header| header == "CompanyName") {
+            Some(Self::Partners)
+        } else if headers.iter().any(|header| header == "StartTime") {
+            Some(Self::Sessions)
+        } else {
+            None
+        }
+    }
+
+    // Human-readable name of a record
+    fn label(self, table: &Table, row: &StringRecord) -> String {
+        match self {
+            Self::Speakers => format!(
+                "{} {}",
+                table.field(row, "FirstName"),
+                table.field(row, "LastName")
+            ),
+            Self::Partners => table.field(row, "CompanyName").to_string(),
+            Self::Sessions => table.field(row, "Title").to_string(),
+        }
+    }
+}
+
+// Records laid out as CSV rows
+#[derive(Debug, Clone, Default)]
+struct Table {
+    headers: StringRecord,
+    rows: Vec<StringRecord>,
+}
+
+impl Table {
+    fn from_records<T: Serialize>(records: &[T]) -> Result<Self> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for record in records {
+            writer
+                .serialize(record)
+                .context("Failed to serialize snapshot record")?;
+        }
+        let bytes = writer
+            .into_inner()
+            .context("Failed to serialize snapshot records")?;
+        Self::from_csv(bytes.as_slice())
+    }
+
+    fn from_csv(reader: impl std::io::Read) -> Result<Self> {
+        let mut reader = csv::Reader::from_reader(reader);
+        let headers = reader
+            .headers()
+            .context("Failed to read CSV header")?
+            .clone();
+        let rows = reader
+            .records()
+            .collect::<Result<_, _>>()
+            .context("Failed to read CSV rows")?;
+        Ok(Self { headers, rows })
+    }
+
+    fn column(&self, name: &str) -> Option<usize> {
+        self.headers.iter().position(|header| header == name)
+    }
+
+    fn field<'a>(&self, row: &'a StringRecord, name: &str) -> &'a str {
+        self.column(name)
+            .and_then(|index| row.get(index))
+            .unwrap_or_default()
+    }
+
+    // The ID, or the company name for partner rows without one, as
+    // `Partner::dedup_key` does
+    fn key(&self, row: &StringRecord) -> String {
+        match self.field(row, "ID") {
+            "" => self.name_key(row),
+            id => id.to_string(),
+        }
+    }
+
+    fn name_key(&self, row: &StringRecord) -> String {
+        format!("name:{}", self.field(row, "CompanyName"))
+    }
+
+    // Blank out fields spelling a missing value
+    fn normalize_nulls(&mut self, null_value: &str) {
+        for row in &mut self.rows {
+            if !row
+                .iter()
+                .any(|field| field == LEGACY_NULL_VALUE || field == null_value)
+            {
+                continue;
+            }
+            *row = row
+                .iter()
+                .map(|field| {
+                    if field == LEGACY_NULL_VALUE || field == null_value {
+                        ""
+                    } else {
+                        field
+                    }
+                })
+                .collect();
+        }
+    }
+}
+
+// Records of one or more entities captured at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    tables: BTreeMap<Entity, Table>,
+}
+
+impl Snapshot {
+    pub fn from_speakers(speakers: Vec<Speaker>) -> Result<Self> {
+        let records = speakers::convert_to_csv_records(speakers);
+        Ok(Self::default().with(Entity::Speakers, Table::from_records(&records)?))
+    }
+
+    pub fn from_partners(partners: Vec<Partner>) -> Result<Self> {
+        let records = partners::convert_to_partner_records(partners);
+        Ok(Self::default().with(Entity::Partners, Table::from_records(&records)?))
+    }
+
+    pub fn from_sessions(sessions: Vec<Session>) -> Result<Self> {
+        let records = sessions::convert_to_session_records(sessions);
+        Ok(Self::default().with(Entity::Sessions, Table::from_records(&records)?))
+    }
+
+    // A snapshot holding no records of `entity`
+    pub fn empty(entity: Entity) -> Self {
+        Self::default().with(entity, Table::default())
+    }
+
+    fn with(mut self, entity: Entity, table: Table) -> Self {
+        self.tables.insert(entity, table);
+        self
+    }
+
+    // Load a CSV, JSON, JSON Lines or SQLite export, by file extension
+    // Fields equal to `null_value` or "N/A" are read as missing.
+    pub fn load(path: &Path, null_value: &str) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let snapshot = match extension.as_str() {
+            "csv" => Self::load_csv(path),
+            "json" | "jsonl" => Self::load_json(path),
+            "sqlite" | "sqlite3" | "db" => Self::load_sqlite(path),
+            _ => anyhow::bail!(
+                "Unsupported snapshot format (expected .csv, .json, .jsonl or .sqlite)"
+            ),
+        };
+        let mut snapshot =
+            snapshot.with_context(|| format!("Failed to load snapshot: {}", path.display()))?;
+        for table in snapshot.tables.values_mut() {
+            table.normalize_nulls(null_value);
+        }
+        Ok(snapshot)
+    }
+
+    fn load_csv(path: &Path) -> Result<Self> {
+        let file = fs::File::open(path).context("Failed to open CSV file")?;
+        let table = Table::from_csv(file)?;
+        let entity = Entity::from_headers(&table.headers)
+            .context("CSV columns match no speakers, partners or sessions")?;
+        Ok(Self::default().with(entity, table))
+    }
+
+    fn load_json(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).context("Failed to read JSON file")?;
+        let items: Vec<serde_json::Value> = if text.trim_start().starts_with('[') {
+            serde_json::from_str(&text).context("Failed to parse JSON array")?
+        } else {
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect::<Result<_, _>>()
+                .context("Failed to parse JSON Lines")?
+        };
+
+        // An empty export holds no entity; `compare` takes the other side's
+        let Some(first) = items.first() else {
+            return Ok(Self::default());
+        };
+        let (is_speaker, is_partner, is_session) = (
+            first.get("firstname").is_some(),
+            first.get("category").is_some(),
+            first.get("start_time").is_some(),
+        );
+        let items = serde_json::Value::Array(items);
+        if is_speaker {
+            Self::from_speakers(serde_json::from_value(items).context("Invalid speaker JSON")?)
+        } else if is_partner {
+            Self::from_partners(serde_json::from_value(items).context("Invalid partner JSON")?)
+        } else if is_session {
+            Self::from_sessions(serde_json::from_value(items).context("Invalid session JSON")?)
+        } else {
+            anyhow::bail!("JSON objects match no speakers, partners or sessions")
+        }
+    }
+
+    fn load_sqlite(path: &Path) -> Result<Self> {
+        let speakers = Self::from_speakers(sqlite::read_speakers(path)?)?;
+        let partners = Self::from_partners(sqlite::read_partners(path)?)?;
+        let sessions = Self::from_sessions(sqlite::read_sessions(path)?)?;
+        let mut snapshot = Self::default();
+        // Keep the entities actually exported into the database
+        for (entity, table) in speakers
+            .tables
+            .into_iter()
+            .chain(partners.tables)
+            .chain(sessions.tables)
+        {
+            if !table.rows.is_empty() {
+                snapshot.tables.insert(entity, table);
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+// A record present in only one of the snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecordRef {
+    pub id: String,
+    pub label: String,
+}
+
+// A field whose value differs between the snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangedRecord {
+    pub id: String,
+    pub label: String,
+    pub changes: Vec<FieldChange>,
+}
+
+// Differences for one entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntityDiff {
+    pub entity: Entity,
+    pub added: Vec<RecordRef>,
+    pub removed: Vec<RecordRef>,
+    pub changed: Vec<ChangedRecord>,
+}
+
+impl EntityDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} added, {} removed, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )
+    }
+}
+
+// Row indexes of `table` by key, in listing order
+fn index_rows(
+    table: &Table,
+    key: impl Fn(&Table, &StringRecord) -> String,
+) -> HashMap<String, Vec<usize>> {
+    let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+    for (position, row) in table.rows.iter().enumerate() {
+        index.entry(key(table, row)).or_default().push(position);
+    }
+    index
+}
+
+fn diff_tables(entity: Entity, old: &Table, new: &Table) -> EntityDiff {
+    let old_rows = index_rows(old, Table::key);
+    // Partners missing an ID on one side are matched by name instead,
+    // so a legacy export without IDs lines up with a current one
+    let old_names = match entity {
+        Entity::Partners => index_rows(old, Table::name_key),
+        Entity::Speakers | Entity::Sessions => HashMap::new(),
+    };
+    let has_id = |table: &Table, row: &StringRecord| !table.field(row, "ID").is_empty();
+    let mut matched = HashSet::new();
+
+    // Columns of the new snapshot first, then any the new one dropped
+    let mut fields: Vec<&str> = new.headers.iter().collect();
+    fields.extend(
+        old.headers
+            .iter()
+            .filter(|header| new.column(header).is_none()),
+    );
+
+    let mut diff = EntityDiff {
+        entity,
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+
+    for row in &new.rows {
+        let id = new.key(row);
+        let label = entity.label(new, row);
+        // Homonyms pair up in listing order
+        let first_unmatched = |index: &HashMap<String, Vec<usize>>, key: &str, by_name: bool| {
+            index
+                .get(key)?
+                .iter()
+                .copied()
+                .filter(|position| !matched.contains(position))
+                .find(|&position| {
+                    !(by_name && has_id(new, row) && has_id(old, &old.rows[position]))
+                })
+        };
+        let Some(index) = first_unmatched(&old_rows, &id, false)
+            .or_else(|| first_unmatched(&old_names, &new.name_key(row), true))
+        else {
+            diff.added.push(RecordRef { id, label });
+            continue;
+        };
+        matched.insert(index);
+        let old_row = &old.rows[index];
+        // A partner matched by name only gained or lost its ID
+        let same_ids = has_id(new, row) == has_id(old, old_row);
+
+        let changes: Vec<FieldChange> = fields
+            .iter()
+            .filter(|&&field| same_ids || field != "ID")
+            .filter_map(|&field| {
+                let (before, after) = (old.field(old_row, field), new.field(row, field));
+                (before != after).then(|| FieldChange {
+                    field: field.to_string(),
+                    old: before.to_string(),
+                    new: after.to_string(),
+                })
+            })
+            .collect();
+        if !changes.is_empty() {
+            diff.changed.push(ChangedRecord { id, label, changes });
+        }
+    }
+
+    for (index, row) in old.rows.iter().enumerate() {
+        if !matched.contains(&index) {
+            diff.removed.push(RecordRef {
+                id: old.key(row),
+                label: entity.label(old, row),
+            });
+        }
+    }
+
+    diff
+}
+
+// Compare every entity present in both snapshots
+// A snapshot without any records stands for an empty one of the other
+// side's entities, whose records are then all added or removed.
+pub fn compare<'a>(old: &'a Snapshot, new: &'a Snapshot) -> Vec<EntityDiff> {
+    let empty = Table::default();
+    let table = |snapshot: &'a Snapshot, entity: Entity| {
+        if snapshot.tables.is_empty() {
+            Some(&empty)
+        } else {
+            snapshot.tables.get(&entity)
+        }
+    };
+    let entities: BTreeSet<Entity> = old
+        .tables
+        .keys()
+        .chain(new.tables.keys())
+        .copied()
+        .collect();
+    entities
+        .into_iter()
+        .filter_map(|entity| {
+            Some(diff_tables(
+                entity,
+                table(old, entity)?,
+                table(new, entity)?,
+            ))
+        })
+        .collect()
+}
+
+// How a diff report is rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Text,
+    Json,
+    Markdown,
+}
+
+pub fn render(diffs: &[EntityDiff], format: ReportFormat) -> Result<String> {
+    match format {
+        ReportFormat::Text => Ok(render_text(diffs)),
+        ReportFormat::Json => {
+            let mut json =
+                serde_json::to_string_pretty(diffs).context("Failed to serialize diff")?;
+            json.push('\n');
+            Ok(json)
+        }
+        ReportFormat::Markdown => Ok(render_markdown(diffs)),
+    }
+}
+
+fn render_text(diffs: &[EntityDiff]) -> String {
+    let mut out = String::new();
+    for diff in diffs {
+        let _ = writeln!(out, "{}: {}", diff.entity.title(), diff.summary());
+        for record in &diff.added {
+            let _ = writeln!(out, "  + {} [{}]", record.label, record.id);
+        }
+        for record in &diff.removed {
+            let _ = writeln!(out, "  - {} [{}]", record.label, record.id);
+        }
+        for record in &diff.changed {
+            let _ = writeln!(out, "  ~ {} [{}]", record.label, record.id);
+            for change in &record.changes {
+                let _ = writeln!(
+                    out,
+                    "      {}: {:?} -> {:?}",
+                    change.field, change.old, change.new
+                );
+            }
+        }
+    }
+    out
+}
+
+// Escape text for a Markdown table cell
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn render_markdown(diffs: &[EntityDiff]) -> String {
+    let mut out = String::new();
+    for diff in diffs {
+        let _ = writeln!(out, "## {}\n\n{}\n", diff.entity.title(), diff.summary());
+        for (heading, records) in [("Added", &diff.added), ("Removed", &diff.removed)] {
+            if records.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "### {heading}\n");
+            for record in records {
+                let _ = writeln!(out, "- {} (`{}`)", record.label, record.id);
+            }
+            out.push('\n');
+        }
+        if !diff.changed.is_empty() {
+            let _ = writeln!(out, "### Changed\n");
+            for record in &diff.changed {
+                let _ = writeln!(out, "#### {} (`{}`)\n", record.label, record.id);
+                let _ = writeln!(out, "| Field | Before | After |\n| --- | --- | --- |");
+                for change in &record.changes {
+                    let _ = writeln!(
+                        out,
+                        "| {} | {} | {} |",
+                        change.field,
+                        cell(&change.old),
+                        cell(&change.new)
+                    );
+                }
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
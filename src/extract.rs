@@ -0,0 +1,450 @@
+// Shared engine for locating JSON data embedded in VivaTech pages
+// Next.js payloads are parsed structurally first (see `nextjs`). Any other data
+// embedded as plain JSON or as JSON escaped inside a JavaScript string literal is
+// enumerated by scanning the remaining page. Each target then picks the
+// candidate that matches its own schema.
+
+use serde::{Deserialize, Deserializer};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::nextjs;
+
+// Keys holding the display name of a nested object
+const NAME_KEYS: [&str; 3] = ["name", "title", "label"];
+
+// Needles marking the start of an embedded JSON array or object
+const RAW_NEEDLES: [&str; 2] = [r#"[{""#, r#"{""#];
+const ESCAPED_NEEDLES: [&str; 2] = [r#"[{\""#, r#"{\""#];
+
+// How the JSON text is embedded in the page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    // Plain JSON text
+    Raw,
+    // JSON escaped inside a JavaScript string literal (quotes written as \")
+    Escaped,
+    // Reassembled React Server Components flight payload
+    Flight,
+}
+
+// A JSON value found in the page
+#[derive(Debug, Clone)]
+pub struct EmbeddedJson {
+    pub offset: usize,
+    pub encoding: Encoding,
+    pub value: Value,
+}
+
+// Bracket-matching state of the JSON text being scanned
+#[derive(Default)]
+struct Scanner {
+    depth: usize,
+    in_string: bool,
+    escape_next: bool,
+}
+
+enum Step {
+    Continue,
+    // A nested array or object opened or closed
+    Open,
+    Close,
+    Complete,
+    Abort,
+}
+
+impl Scanner {
+    // Feed one decoded JSON character
+    fn feed(&mut self, ch: char) -> Step {
+        if self.in_string {
+            if self.escape_next {
+                self.escape_next = false;
+            } else if ch == '\\' {
+                self.escape_next = true;
+            } else if ch == '"' {
+                self.in_string = false;
+            }
+            return Step::Continue;
+        }
+
+        match ch {
+            '"' => self.in_string = true,
+            '[' | '{' => {
+                self.depth += 1;
+                return Step::Open;
+            }
+            ']' | '}' => {
+                if self.depth == 0 {
+                    return Step::Abort;
+                }
+                self.depth -= 1;
+                return if self.depth == 0 {
+                    Step::Complete
+                } else {
+                    Step::Close
+                };
+            }
+            // Markup outside a string means we left the JSON text
+            '<' => return Step::Abort,
+            _ => {}
+        }
+        Step::Continue
+    }
+}
+
+// A candidate that never completed
+// Candidates starting inside it are nested values: each either closed before
+// the scan stopped or fails at the same point, so they need no scan of their own.
+struct FailedScan {
+    // Where the scan gave up
+    stop: usize,
+    // Start and end (exclusive) of every nested value that closed
+    closed: HashMap<usize, usize>,
+}
+
+// Find the end (exclusive) of the JSON value starting at `start`
+fn find_value_end(
+    html_content: &str,
+    start: usize,
+    encoding: Encoding,
+) -> Result<usize, FailedScan> {
+    let mut scanner = Scanner::default();
+    let mut opened = Vec::new();
+    let mut closed = HashMap::new();
+    let failed = |stop: usize, closed| Err(FailedScan { stop, closed });
+    let mut chars = html_content[start..].char_indices();
+
+    while let Some((i, ch)) = chars.next() {
+        let decoded = match (encoding, ch) {
+            (Encoding::Escaped, '\\') => match chars.next() {
+                Some((_, next_ch @ ('"' | '\\'))) => next_ch,
+                Some((_, 'u')) => {
+                    let hex_chars: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                    match u32::from_str_radix(&hex_chars, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                    {
+                        Some(unicode_char @ ('"' | '\\')) => unicode_char,
+                        // Any other code unit (including surrogate halves) is plain text
+                        _ if hex_chars.len() == 4 => 'x',
+                        _ => return failed(start + i, closed),
+                    }
+                }
+                Some(_) => 'x',
+                None => return failed(html_content.len(), closed),
+            },
+            // An unescaped quote terminates the surrounding JavaScript string
+            (Encoding::Escaped, '"') => return failed(start + i, closed),
+            _ => ch,
+        };
+
+        let end = start + i + ch.len_utf8();
+        match scanner.feed(decoded) {
+            Step::Continue => {}
+            Step::Open => opened.push(start + i),
+            Step::Close => {
+                if let Some(open) = opened.pop() {
+                    closed.insert(open, end);
+                }
+            }
+            Step::Abort => return failed(start + i, closed),
+            Step::Complete => return Ok(end),
+        }
+    }
+
+    failed(html_content.len(), closed)
+}
+
+// Decode the body of a JSON string literal (the text between the quotes)
+// Handles every JSON escape, including \/, \b, \f and UTF-16 surrogate pairs.
+pub fn decode_string_literal(text: &str) -> Option<String> {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    literal.push_str(text);
+    literal.push('"');
+
+    match serde_json::from_str::<String>(&literal) {
+        Ok(decoded) => Some(decoded),
+        Err(e) => {
+            log::debug!("Failed to decode escaped JSON string: {e}");
+            None
+        }
+    }
+}
+
+// Decode the embedded text into a JSON value
+fn decode(text: &str, encoding: Encoding) -> Option<Value> {
+    match encoding {
+        Encoding::Raw | Encoding::Flight => serde_json::from_str(text).ok(),
+        Encoding::Escaped => {
+            decode_string_literal(text).and_then(|unescaped| serde_json::from_str(&unescaped).ok())
+        }
+    }
+}
+
+// Next occurrence of every needle of either encoding
+// Each needle's offset is cached and only searched again once the scan has
+// moved past it, so the page is read through once per needle.
+struct Needles<'a> {
+    html_content: &'a str,
+    next: Vec<(&'static str, Encoding, Option<usize>)>,
+}
+
+impl<'a> Needles<'a> {
+    fn new(html_content: &'a str) -> Self {
+        let needles = RAW_NEEDLES
+            .iter()
+            .map(|needle| (*needle, Encoding::Raw))
+            .chain(
+                ESCAPED_NEEDLES
+                    .iter()
+                    .map(|needle| (*needle, Encoding::Escaped)),
+            );
+        Self {
+            html_content,
+            next: needles
+                .map(|(needle, encoding)| (needle, encoding, html_content.find(needle)))
+                .collect(),
+        }
+    }
+
+    // Find the first needle at or after `from`, raw needles winning ties
+    fn next_start(&mut self, from: usize) -> Option<(usize, Encoding)> {
+        for (needle, _, next) in &mut self.next {
+            if next.is_some_and(|offset| offset < from) {
+                *next = self
+                    .html_content
+                    .get(from..)
+                    .and_then(|haystack| haystack.find(*needle))
+                    .map(|idx| from + idx);
+            }
+        }
+        self.next
+            .iter()
+            .filter_map(|(_, encoding, next)| next.map(|offset| (offset, *encoding)))
+            .min_by_key(|(offset, _)| *offset)
+    }
+}
+
+// Enumerate every top-level JSON array or object embedded in the page
+pub fn find_embedded_json(html_content: &str) -> Vec<EmbeddedJson> {
+    let document = nextjs::parse_next_document(html_content);
+    let mut found = Vec::new();
+
+    if let Some(tree) = document.flight_tree() {
+        found.push(EmbeddedJson {
+            offset: document.spans.first().map_or(0, |span| span.start),
+            encoding: Encoding::Flight,
+            value: tree,
+        });
+    }
+    if let Some(next_data) = document.next_data {
+        found.push(EmbeddedJson {
+            offset: document.spans.last().map_or(0, |span| span.start),
+            encoding: Encoding::Raw,
+            value: next_data,
+        });
+    }
+
+    found.extend(scan_embedded_json(html_content, &document.spans));
+
+    log::info!("Found {} embedded JSON values", found.len());
+    found
+}
+
+// Scan the page for JSON outside the already parsed Next.js payload scripts
+fn scan_embedded_json(html_content: &str, skip: &[Range<usize>]) -> Vec<EmbeddedJson> {
+    let mut found = Vec::new();
+    let mut needles = Needles::new(html_content);
+    let mut failures: Vec<(Encoding, FailedScan)> = Vec::new();
+    let mut pos = 0;
+
+    while let Some((start, encoding)) = needles.next_start(pos) {
+        if let Some(span) = skip.iter().find(|span| span.contains(&start)) {
+            pos = span.end;
+            continue;
+        }
+
+        // Rescanning the rest of a failed candidate for each nested one would
+        // take quadratic time on large pages
+        let inside_failure = failures.iter().find(|(failed_encoding, failure)| {
+            *failed_encoding == encoding && start < failure.stop
+        });
+        let end = match inside_failure {
+            Some((_, failure)) => failure.closed.get(&start).copied(),
+            None => match find_value_end(html_content, start, encoding) {
+                Ok(end) => Some(end),
+                Err(failure) => {
+                    failures.retain(|(failed_encoding, _)| *failed_encoding != encoding);
+                    failures.push((encoding, failure));
+                    None
+                }
+            },
+        };
+        let decoded = end
+            .and_then(|end| decode(&html_content[start..end], encoding).map(|value| (end, value)));
+
+        match decoded {
+            Some((end, value)) => {
+                log::debug!("Found embedded {encoding:?} JSON at offset {start}");
+                found.push(EmbeddedJson {
+                    offset: start,
+                    encoding,
+                    value,
+                });
+                pos = end;
+            }
+            None => pos = start + 1,
+        }
+    }
+
+    found
+}
+
+// Collect every array nested anywhere inside a JSON value
+fn collect_arrays<'a>(value: &'a Value, arrays: &mut Vec<&'a Vec<Value>>) {
+    match value {
+        Value::Array(items) => {
+            arrays.push(items);
+            for item in items {
+                collect_arrays(item, arrays);
+            }
+        }
+        Value::Object(obj) => {
+            for item in obj.values() {
+                collect_arrays(item, arrays);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Pick the array whose objects best match a target schema
+// Returns the candidate with the most matching objects, if any matched at all.
+pub fn find_object_array<F>(documents: &[EmbeddedJson], matches: F) -> Option<&[Value]>
+where
+    F: Fn(&Map<String, Value>) -> bool,
+{
+    let mut arrays = Vec::new();
+    for document in documents {
+        collect_arrays(&document.value, &mut arrays);
+    }
+
+    arrays
+        .into_iter()
+        .map(|array| {
+            let count = array
+                .iter()
+                .filter_map(Value::as_object)
+                .filter(|obj| matches(obj))
+                .count();
+            (count, array)
+        })
+        .filter(|(count, _)| *count > 0)
+        // Prefer the earliest candidate when counts tie
+        .rev()
+        .max_by_key(|(count, _)| *count)
+        .map(|(_, array)| array.as_slice())
+}
+
+// Collect every object whose `id` equals `id`, in document order
+pub fn find_objects_by_id<'a>(
+    documents: &'a [EmbeddedJson],
+    id: &str,
+) -> Vec<&'a Map<String, Value>> {
+    fn walk<'a>(value: &'a Value, id: &str, found: &mut Vec<&'a Map<String, Value>>) {
+        match value {
+            Value::Object(obj) => {
+                if obj.get("id").and_then(id_string).as_deref() == Some(id) {
+                    found.push(obj);
+                }
+                for item in obj.values() {
+                    walk(item, id, found);
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    walk(item, id, found);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut found = Vec::new();
+    for document in documents {
+        walk(&document.value, id, &mut found);
+    }
+    found
+}
+
+// First non-empty string among `keys`
+pub fn first_string(obj: &Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| obj.get(*key).and_then(Value::as_str))
+        .map(str::trim)
+        .find(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+// First non-empty name among `keys`, given either as a string or an object
+pub fn first_name(obj: &Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| obj.get(*key))
+        .find_map(display_name)
+}
+
+// First non-empty value among `keys`, given either as a string or a number
+pub fn first_scalar(obj: &Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| obj.get(*key))
+        .find_map(|value| match value {
+            Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+}
+
+// Display name of a string or of an object carrying a name
+pub fn display_name(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Object(obj) => first_string(obj, &NAME_KEYS),
+        _ => None,
+    }
+}
+
+// ID given as a string, a number or an object carrying an `id`
+pub fn id_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Object(obj) => obj.get("id").and_then(id_string),
+        _ => None,
+    }
+}
+
+// Names of an array of strings or named objects
+pub fn name_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| items.iter().filter_map(display_name).collect())
+        .unwrap_or_default()
+}
+
+// IDs of an array of ID strings or objects carrying an `id`
+pub fn id_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| items.iter().filter_map(id_string).collect())
+        .unwrap_or_default()
+}
+
+// Deserialize an optional string field, treating an empty string as missing
+pub fn non_empty_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?.filter(|value| !value.is_empty()))
+}
@@ -0,0 +1,307 @@
+// HTTP fetching for VivaTech pages
+
+use anyhow::{Context, Result};
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::cache::{ResponseCache, Validators};
+
+// Constants
+pub const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36";
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// How failed page fetches are retried
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    // Retries after the first attempt
+    pub max_retries: u32,
+    // Delay before the first retry, doubled on every further retry
+    pub base_delay: Duration,
+    // Upper bound for a single backoff delay
+    pub max_delay: Duration,
+    // Total time allowed for all attempts and delays
+    pub budget: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            budget: Duration::from_secs(120),
+        }
+    }
+}
+
+impl RetryPolicy {
+    // Never retry
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    // Backoff before retry number `retry` (starting at 0), with jitter
+    // The delay is drawn uniformly from the upper half of the exponential step.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        let jitter_ms = u64::try_from(half.as_millis()).unwrap_or(u64::MAX);
+        half + Duration::from_millis(fastrand::u64(0..=jitter_ms))
+    }
+}
+
+// Successful outcome of a (possibly conditional) request
+enum Fetched {
+    Body {
+        content: String,
+        validators: Validators,
+    },
+    NotModified,
+}
+
+// Why a single request attempt failed
+pub(crate) enum AttemptError {
+    // Transient failure worth another attempt
+    Retryable {
+        error: anyhow::Error,
+        retry_after: Option<Duration>,
+    },
+    // Permanent failure
+    Fatal(anyhow::Error),
+}
+
+impl AttemptError {
+    fn retryable(error: anyhow::Error) -> Self {
+        Self::Retryable {
+            error,
+            retry_after: None,
+        }
+    }
+
+    // A request that could not be sent, retryable if the failure is transient
+    pub(crate) fn send_failed(e: reqwest::Error) -> Self {
+        let transient = e.is_timeout() || e.is_connect() || e.is_request();
+        let error = anyhow::Error::new(e).context("Failed to send HTTP request");
+        if transient {
+            Self::retryable(error)
+        } else {
+            Self::Fatal(error)
+        }
+    }
+
+    // A POST that could not be completed, retryable only if it never reached
+    // the server: after a timeout it may have been received and acted upon
+    pub(crate) fn post_failed(e: reqwest::Error) -> Self {
+        let unsent = e.is_connect();
+        let error = anyhow::Error::new(e).context("Failed to send HTTP request");
+        if unsent {
+            Self::retryable(error)
+        } else {
+            Self::Fatal(error)
+        }
+    }
+
+    // A response with a non-success status
+    pub(crate) fn status(response: &Response) -> Self {
+        let status = response.status();
+        let error = anyhow::anyhow!("Server returned non-success status code: {}", status);
+        if is_retryable_status(status) {
+            Self::Retryable {
+                error,
+                retry_after: parse_retry_after(response),
+            }
+        } else {
+            Self::Fatal(error)
+        }
+    }
+}
+
+// Build the blocking HTTP client used for all page fetches
+pub fn build_client(timeout: Duration) -> Result<Client> {
+    Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(timeout)
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+// Statuses worth retrying: rate limiting, timeouts and transient server errors
+const fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 408 | 425 | 429 | 500 | 502 | 503 | 504)
+}
+
+// Parse a Retry-After header given either in seconds or as an HTTP date
+fn parse_retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+fn header_value(response: &Response, name: reqwest::header::HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+// Make one request, classifying failures as retryable or fatal
+fn attempt_fetch(
+    client: &Client,
+    url: &str,
+    validators: Option<&Validators>,
+) -> Result<Fetched, AttemptError> {
+    let mut request = client.get(url);
+    if let Some(validators) = validators {
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = request.send().map_err(AttemptError::send_failed)?;
+
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED && validators.is_some() {
+        return Ok(Fetched::NotModified);
+    }
+    if !status.is_success() {
+        return Err(AttemptError::status(&response));
+    }
+
+    let validators = Validators {
+        etag: header_value(&response, ETAG),
+        last_modified: header_value(&response, LAST_MODIFIED),
+    };
+    let content = response.text().map_err(|e| {
+        let transient = e.is_timeout();
+        let error = anyhow::Error::new(e).context("Failed to read response body as text");
+        if transient {
+            AttemptError::retryable(error)
+        } else {
+            AttemptError::Fatal(error)
+        }
+    })?;
+
+    Ok(Fetched::Body {
+        content,
+        validators,
+    })
+}
+
+// Run a request, retrying transient failures according to the policy
+fn fetch_with_retries(
+    client: &Client,
+    url: &str,
+    policy: &RetryPolicy,
+    validators: Option<&Validators>,
+) -> Result<Fetched> {
+    with_retries(url, policy, || attempt_fetch(client, url, validators))
+}
+
+// Make `attempt`s at a request until one succeeds, fails for good, or the
+// policy runs out of retries or time; `target` names the request in the logs
+pub(crate) fn with_retries<T>(
+    target: &str,
+    policy: &RetryPolicy,
+    mut attempt_request: impl FnMut() -> Result<T, AttemptError>,
+) -> Result<T> {
+    let started = Instant::now();
+    let attempts = policy.max_retries + 1;
+    let mut attempt = 1;
+
+    loop {
+        log::info!("Attempt {attempt}/{attempts} for {target}");
+
+        let (error, retry_after) = match attempt_request() {
+            Ok(value) => return Ok(value),
+            Err(AttemptError::Fatal(error)) => return Err(error),
+            Err(AttemptError::Retryable { error, retry_after }) => (error, retry_after),
+        };
+
+        if attempt == attempts {
+            return Err(error.context(format!("Giving up after {attempts} attempts")));
+        }
+
+        let delay = retry_after.unwrap_or_else(|| policy.backoff(attempt - 1));
+        if started.elapsed() + delay > policy.budget {
+            return Err(error.context(format!(
+                "Retry budget of {:?} exhausted after {attempt} attempts",
+                policy.budget
+            )));
+        }
+
+        log::warn!(
+            "Attempt {attempt}/{attempts} for {target} failed: {error:#}; retrying in {delay:?}"
+        );
+        thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+// Fetch a page and return its body as text, retrying transient failures
+// With a cache, fresh entries are reused as-is and stale ones are revalidated
+// with a conditional request.
+pub fn fetch_page_content(
+    client: &Client,
+    url: &str,
+    policy: &RetryPolicy,
+    cache: Option<&ResponseCache>,
+) -> Result<String> {
+    log::info!("Fetching content from URL: {url}");
+
+    let cached = cache.and_then(|cache| cache.lookup(url));
+    if let (Some(cache), Some(entry)) = (cache, &cached) {
+        if entry.age() < cache.max_age() {
+            log::info!("Using cached copy of {url} ({:?} old)", entry.age());
+            return Ok(entry.body.clone());
+        }
+    }
+
+    let validators = cached
+        .as_ref()
+        .map(|entry| &entry.validators)
+        .filter(|validators| !validators.is_empty());
+
+    match fetch_with_retries(client, url, policy, validators)? {
+        Fetched::Body {
+            content,
+            validators,
+        } => {
+            log::info!("Successfully fetched {} bytes of content", content.len());
+            if let Some(cache) = cache {
+                if let Err(e) = cache.store(url, &validators, &content) {
+                    log::warn!("Failed to cache response for {url}: {e:#}");
+                }
+            }
+            Ok(content)
+        }
+        Fetched::NotModified => {
+            let entry = cached.context("Server returned 304 Not Modified without a cached copy")?;
+            log::info!("{url} not modified, reusing cached copy");
+            if let Some(cache) = cache {
+                if let Err(e) = cache.refresh(url, &entry.validators) {
+                    log::warn!("Failed to refresh cache entry for {url}: {e:#}");
+                }
+            }
+            Ok(entry.body)
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// VivaTech conference data scraping library
+// Fetches the public VivaTech pages, extracts the embedded JSON data and
+// converts it into typed speaker, partner and session models ready for export.
+
+pub mod cache;
+pub mod cities;
+pub mod countries;
+pub mod details;
+pub mod diff;
+pub mod extract;
+pub mod fetch;
+pub mod nextjs;
+pub mod output;
+pub mod parquet;
+pub mod partners;
+pub mod schedule;
+pub mod scraper;
+pub mod sessions;
+pub mod speakers;
+pub mod sqlite;
+pub mod store;
+pub mod webhook;
+pub mod xlsx;
+
+pub use cities::{City, CityResolution};
+pub use countries::Country;
+pub use partners::{
+    CategoryFilter, Partner, PartnerDetailRecord, PartnerDetails, PartnerRecord, TeamMember,
+};
+pub use scraper::{Page, PageSource, Scraper};
+pub use sessions::{Session, SessionRecord};
+pub use speakers::{Image, Speaker, SpeakerDetailRecord, SpeakerDetails, SpeakerRecord};
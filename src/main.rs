@@ -1,19 +1,26 @@
 // VivaTech conference speaker scraper
-// Extracts speaker data from embedded JSON in the website
-
-mod partners;
+// Command-line client of the vivatech scraping library
 
 use anyhow::{Context, Result};
-use clap::Parser;
-use serde::{Deserialize, Serialize};
+use chrono::Utc;
+use clap::{Args, FromArgMatches, Parser, Subcommand};
+use serde::Serialize;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
-
-// Constants
-const DEFAULT_OUTPUT_FILE: &str = "vivatech_speakers_2025_extended.csv";
-const TARGET_URL: &str = "https://vivatechnology.com/speakers";
-const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36";
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use vivatech::cache::{self, ResponseCache};
+use vivatech::details;
+use vivatech::diff::{self, Entity, ReportFormat, Snapshot};
+use vivatech::fetch::RetryPolicy;
+use vivatech::output::{self, OutputFormat};
+use vivatech::schedule::Schedule;
+use vivatech::store::{RecordChange, SnapshotInfo, SnapshotStore};
+use vivatech::webhook::{self, ChangeEvent, Notifier, Webhook, WebhookKind};
+use vivatech::{parquet, sqlite, xlsx};
+use vivatech::{partners, sessions, speakers, CategoryFilter, Page, PageSource, Scraper};
 
 #[derive(Parser)]
 #[command(
@@ -24,217 +31,511 @@ const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/
                   It targets embedded JSON data for reliability and exports the results to CSV format."
 )]
 struct Cli {
-    /// What to scrape: 'speakers' or 'partners'
-    #[arg(value_enum, default_value = "speakers")]
-    target: ScrapeTarget,
-
-    /// Output CSV file path (defaults depend on target)
-    #[arg(short, long)]
-    output: Option<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
 
     /// Enable verbose logging for debugging
-    #[arg(short, long, action = clap::ArgAction::Count)]
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
     verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Scrape speakers (the default)
+    Speakers(SpeakersArgs),
+    /// Scrape partners and exhibitors
+    Partners(PartnersArgs),
+    /// Scrape the session agenda
+    Sessions(ScrapeArgs),
+    /// Scrape speakers and partners into one Excel file
+    Workbook(WorkbookArgs),
+    /// List the partner types with their counts
+    Categories(CategoriesArgs),
+    /// Compare two exports (CSV, JSON, JSON Lines or SQLite)
+    Diff(DiffArgs),
+    /// List the snapshots of a store, oldest first
+    Snapshots(StoreArgs),
+    /// Show in which snapshots a record was added, changed or removed
+    History(HistoryArgs),
+    /// Write a past snapshot in any format
+    Export(ExportArgs),
+    /// Scrape speakers, partners and sessions on a schedule
+    Watch(WatchArgs),
+}
 
+// Where the pages of a single target are read from
+#[derive(Args)]
+struct SourceArgs {
     /// Override the target URL (mainly for testing purposes)
     #[arg(long, hide = true)]
     url: Option<String>,
+
+    /// Extract from a saved HTML page instead of fetching the live site
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["url", "input_dir"])]
+    input: Option<PathBuf>,
+
+    /// Extract from every saved .html page in a directory
+    #[arg(long, value_name = "DIR", conflicts_with = "url")]
+    input_dir: Option<PathBuf>,
 }
 
-#[derive(Debug, Clone, Copy, clap::ValueEnum)]
-enum ScrapeTarget {
-    Speakers,
-    Partners,
-}
-
-// Speaker data model matching JSON structure
-#[derive(Debug, Deserialize, Serialize)]
-#[allow(clippy::struct_excessive_bools)]
-struct Speaker {
-    id: String,
-    firstname: String,
-    lastname: String,
-    #[serde(default)]
-    email: String,
-    #[serde(rename = "jobTitle")]
-    job_title: String,
-    company: String,
-    #[serde(default)]
-    tags: Vec<String>,
-    #[serde(default)]
-    themes: Vec<String>,
-    image: Option<Image>,
-    #[serde(rename = "hasBio", default)]
-    has_bio: bool,
-    #[serde(rename = "hasSessions", default)]
-    has_sessions: bool,
-    #[serde(rename = "isOfficial", default)]
-    is_official: bool,
-    #[serde(rename = "isPartner", default)]
-    is_partner: bool,
-    #[serde(default)]
-    top: bool,
-    #[serde(default)]
-    communication_manager: Option<String>,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-struct Image {
-    #[serde(default)]
-    s: String,
-    #[serde(default)]
-    t: String,
-    #[serde(default)]
-    l: String,
-    u: String,
-}
-
-// CSV output format
-#[derive(Debug, Serialize)]
-#[allow(clippy::struct_excessive_bools)]
-struct SpeakerRecord {
-    #[serde(rename = "ID")]
-    id: String,
-    #[serde(rename = "FirstName")]
-    first_name: String,
-    #[serde(rename = "LastName")]
-    last_name: String,
-    #[serde(rename = "Email")]
-    email: String,
-    #[serde(rename = "JobTitle")]
-    job_title: String,
-    #[serde(rename = "Company")]
-    company: String,
-    #[serde(rename = "Tags")]
-    tags: String,
-    #[serde(rename = "Themes")]
-    themes: String,
-    #[serde(rename = "HasBio")]
-    has_bio: bool,
-    #[serde(rename = "HasSessions")]
-    has_sessions: bool,
-    #[serde(rename = "IsOfficial")]
-    is_official: bool,
-    #[serde(rename = "IsPartner")]
-    is_partner: bool,
-    #[serde(rename = "IsTopSpeaker")]
-    is_top_speaker: bool,
-    #[serde(rename = "CommunicationManager")]
-    communication_manager: String,
-    #[serde(rename = "ImageSmallURL")]
-    image_small_url: String,
-    #[serde(rename = "ImageThumbnailURL")]
-    image_thumbnail_url: String,
-    #[serde(rename = "ImageLargeURL")]
-    image_large_url: String,
-    #[serde(rename = "ImageMainURL")]
-    image_main_url: String,
-}
-
-fn fetch_page_content(url: &str) -> Result<String> {
-    let client = reqwest::blocking::Client::builder()
-        .user_agent(USER_AGENT)
-        .build()
-        .context("Failed to build HTTP client")?;
-
-    log::info!("Fetching content from URL: {url}");
-
-    let response = client
-        .get(url)
-        .send()
-        .context("Failed to send HTTP request")?;
-
-    let status = response.status();
-    if !status.is_success() {
-        anyhow::bail!("Server returned non-success status code: {}", status);
-    }
-
-    let content = response
-        .text()
-        .context("Failed to read response body as text")?;
-
-    log::info!("Successfully fetched {} bytes of content", content.len());
-    Ok(content)
-}
-
-// Unescape Unicode sequences like \u0026 to actual characters
-fn unescape_unicode(input: &str) -> String {
-    let mut result = String::new();
-    let mut chars = input.chars();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\\' {
-            if let Some(next_ch) = chars.next() {
-                match next_ch {
-                    'u' => {
-                        let hex_chars: String = chars.by_ref().take(4).collect();
-                        if hex_chars.len() == 4 {
-                            if let Ok(code_point) = u32::from_str_radix(&hex_chars, 16) {
-                                if let Some(unicode_char) = char::from_u32(code_point) {
-                                    result.push(unicode_char);
-                                    continue;
-                                }
-                            }
-                        }
-                        // If parsing failed, add the original sequence
-                        result.push('\\');
-                        result.push('u');
-                        result.push_str(&hex_chars);
-                    }
-                    'n' => result.push('\n'),
-                    'r' => result.push('\r'),
-                    't' => result.push('\t'),
-                    '"' => result.push('"'),
-                    '\\' => result.push('\\'),
-                    _ => {
-                        result.push('\\');
-                        result.push(next_ch);
-                    }
-                }
-            } else {
-                result.push(ch);
-            }
+// Where the pages of several targets are read from: one URL or page cannot
+// stand for them all
+#[derive(Args)]
+struct SourcesArgs {
+    /// Extract from the saved .html pages of every target in a directory
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+}
+
+// How pages are fetched
+#[derive(Args)]
+struct FetchArgs {
+    /// HTTP request timeout in seconds
+    #[arg(long, value_name = "SECS", default_value_t = 30)]
+    timeout: u64,
+
+    /// Number of retries for rate-limited or failed requests
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    retries: u32,
+
+    /// Base delay before the first retry in milliseconds, doubled on each retry
+    #[arg(long, value_name = "MS", default_value_t = 1000)]
+    retry_delay: u64,
+
+    /// Total time budget for all fetch attempts in seconds
+    #[arg(long, value_name = "SECS", default_value_t = 120)]
+    retry_budget: u64,
+
+    /// Directory of the on-disk HTTP response cache
+    #[arg(long, value_name = "DIR", default_value = cache::DEFAULT_CACHE_DIR)]
+    cache_dir: PathBuf,
+
+    /// Reuse cached pages younger than this many seconds without revalidating
+    #[arg(long, value_name = "SECS", default_value_t = 0)]
+    max_age: u64,
+
+    /// Always download pages and never read or write the response cache
+    #[arg(long)]
+    no_cache: bool,
+}
+
+// How scraped data is formatted
+#[derive(Args)]
+struct FormatArgs {
+    /// Output format: 'csv', 'json' (array), 'jsonl' (one object per line), 'sqlite', 'parquet' or 'xlsx'
+    #[arg(long, value_enum, default_value = "csv")]
+    format: OutputFormat,
+
+    /// Text written for missing values in CSV output (other formats use native nulls)
+    #[arg(long, value_name = "TEXT", default_value = "")]
+    null_value: String,
+}
+
+// Where and how scraped data is written
+#[derive(Args)]
+struct OutputArgs {
+    /// Output file path (defaults depend on target and format)
+    #[arg(short, long)]
+    output: Option<String>,
+
+    #[command(flatten)]
+    format: FormatArgs,
+}
+
+// Which detail pages are crawled
+#[derive(Args)]
+struct DetailArgs {
+    /// Also follow each item's detail page and write a companion details CSV
+    /// (detail pages are always fetched, so offline --input runs cannot use it)
+    #[arg(long, conflicts_with_all = ["input", "input_dir"])]
+    with_details: bool,
+
+    /// Base URL of the detail pages (defaults to the listing URL)
+    #[arg(long, value_name = "URL", requires = "with_details")]
+    details_url: Option<String>,
+
+    /// Maximum number of detail pages fetched in parallel
+    #[arg(long, value_name = "N", default_value_t = details::DEFAULT_CONCURRENCY)]
+    concurrency: usize,
+}
+
+// Which partners are kept
+#[derive(Args)]
+struct CategoryArgs {
+    /// Partner type to include, repeatable ('all' keeps every type; defaults to partners and startups)
+    #[arg(long = "category", value_name = "TYPE")]
+    categories: Vec<String>,
+}
+
+// Who hears about the changes recorded in a snapshot store
+#[derive(Args)]
+struct WebhookArgs {
+    /// Generic webhook receiving added, changed and removed records as JSON (repeatable)
+    #[arg(long, value_name = "URL", requires = "store")]
+    webhook: Vec<String>,
+
+    /// Slack-compatible webhook receiving the message as `text` (repeatable)
+    #[arg(long, value_name = "URL", requires = "store")]
+    slack_webhook: Vec<String>,
+
+    /// Webhook message, with {entity}, {added}, {changed}, {removed}, {records}, {snapshot},
+    /// {source} and {changes} placeholders
+    #[arg(long, value_name = "TEXT", default_value = webhook::DEFAULT_TEMPLATE)]
+    webhook_template: String,
+
+    /// Secret for the HMAC-SHA256 signature sent in the X-Vivatech-Signature-256 header
+    #[arg(
+        long,
+        value_name = "SECRET",
+        env = "VIVATECH_WEBHOOK_SECRET",
+        hide_env_values = true
+    )]
+    webhook_secret: Option<String>,
+}
+
+// Where a single scrape is recorded, if anywhere
+#[derive(Args)]
+struct RecordArgs {
+    /// Snapshot store directory: every scrape is recorded there
+    #[arg(long, value_name = "DIR")]
+    store: Option<PathBuf>,
+
+    #[command(flatten)]
+    webhooks: WebhookArgs,
+}
+
+// The snapshot store read by the commands browsing it
+#[derive(Args)]
+struct StoreArgs {
+    /// Snapshot store directory
+    #[arg(long, value_name = "DIR")]
+    store: PathBuf,
+}
+
+// When 'watch' rounds start
+#[derive(Args)]
+struct ScheduleArgs {
+    /// Seconds between the starts of rounds
+    #[arg(long, value_name = "SECS", default_value_t = 3600)]
+    interval: u64,
+
+    /// Cron expression scheduling rounds instead of --interval, in UTC (e.g. '0 */6 * * *')
+    #[arg(long, value_name = "EXPR", conflicts_with = "interval")]
+    cron: Option<String>,
+}
+
+#[derive(Args)]
+struct ScrapeArgs {
+    #[command(flatten)]
+    source: SourceArgs,
+
+    #[command(flatten)]
+    fetch: FetchArgs,
+
+    #[command(flatten)]
+    output: OutputArgs,
+
+    #[command(flatten)]
+    record: RecordArgs,
+}
+
+#[derive(Args)]
+struct SpeakersArgs {
+    #[command(flatten)]
+    scrape: ScrapeArgs,
+
+    #[command(flatten)]
+    details: DetailArgs,
+}
+
+#[derive(Args)]
+struct PartnersArgs {
+    #[command(flatten)]
+    scrape: ScrapeArgs,
+
+    #[command(flatten)]
+    details: DetailArgs,
+
+    #[command(flatten)]
+    categories: CategoryArgs,
+}
+
+#[derive(Args)]
+struct WorkbookArgs {
+    #[command(flatten)]
+    sources: SourcesArgs,
+
+    #[command(flatten)]
+    fetch: FetchArgs,
+
+    /// Output file path (defaults to vivatech_2025.xlsx; always Excel)
+    #[arg(short, long)]
+    output: Option<String>,
+
+    #[command(flatten)]
+    categories: CategoryArgs,
+
+    #[command(flatten)]
+    record: RecordArgs,
+}
+
+#[derive(Args)]
+struct CategoriesArgs {
+    #[command(flatten)]
+    source: SourceArgs,
+
+    #[command(flatten)]
+    fetch: FetchArgs,
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    /// Older export
+    old: PathBuf,
+
+    /// Newer export
+    new: PathBuf,
+
+    /// Report format: 'text', 'json' or 'markdown'
+    #[arg(long, value_enum, default_value = "text")]
+    report: ReportFormat,
+
+    /// Report file path (defaults to stdout)
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// Text read as a missing value, besides empty fields and "N/A"
+    #[arg(long, value_name = "TEXT", default_value = "")]
+    null_value: String,
+}
+
+#[derive(Args)]
+struct HistoryArgs {
+    /// ID of the speaker, partner or session
+    record_id: String,
+
+    #[command(flatten)]
+    store: StoreArgs,
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// Snapshot ID, a unique prefix of one, or 'latest'
+    snapshot_id: String,
+
+    #[command(flatten)]
+    store: StoreArgs,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    #[command(flatten)]
+    sources: SourcesArgs,
+
+    #[command(flatten)]
+    fetch: FetchArgs,
+
+    #[command(flatten)]
+    format: FormatArgs,
+
+    #[command(flatten)]
+    categories: CategoryArgs,
+
+    #[command(flatten)]
+    store: StoreArgs,
+
+    #[command(flatten)]
+    webhooks: WebhookArgs,
+
+    /// Format of the changes printed after each round: 'text', 'json' or 'markdown'
+    #[arg(long, value_enum, default_value = "text")]
+    report: ReportFormat,
+
+    #[command(flatten)]
+    schedule: ScheduleArgs,
+}
+
+// How detail pages are crawled with --with-details
+struct DetailOptions {
+    base_url: String,
+    concurrency: usize,
+}
+
+// Where scrapes are recorded with --store, who hears about changes, and how
+// changes are printed
+struct SnapshotOptions {
+    store: SnapshotStore,
+    notifier: Option<Notifier>,
+    report: Option<ReportFormat>,
+}
+
+impl SpeakersArgs {
+    fn defaults() -> Result<Self> {
+        let command = Self::augment_args(clap::Command::new("speakers"));
+        Ok(Self::from_arg_matches(
+            &command.get_matches_from(["speakers"]),
+        )?)
+    }
+}
+
+impl SourceArgs {
+    // Resolve where pages are read from, defaulting to the live site
+    fn page_source(&self, default_url: &str) -> PageSource {
+        if let Some(path) = &self.input {
+            PageSource::File(path.clone())
+        } else if let Some(dir) = &self.input_dir {
+            PageSource::Directory(dir.clone())
         } else {
-            result.push(ch);
+            PageSource::Url(self.url.clone().unwrap_or_else(|| default_url.to_string()))
         }
     }
+}
 
-    result
+impl SourcesArgs {
+    fn page_source(&self, default_url: &str) -> PageSource {
+        self.input_dir.clone().map_or_else(
+            || PageSource::Url(default_url.to_string()),
+            PageSource::Directory,
+        )
+    }
 }
 
-// Extract JSON data from HTML - looks for escaped JSON array pattern
-fn extract_json_from_html(html_content: &str) -> Result<String> {
-    if let Some(start_idx) = html_content.find(r#"[{\"id\":\""#) {
-        let mut bracket_count = 0;
-        let mut in_string = false;
-        let mut escape_next = false;
+impl FetchArgs {
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.retries,
+            base_delay: Duration::from_millis(self.retry_delay),
+            budget: Duration::from_secs(self.retry_budget),
+            ..RetryPolicy::default()
+        }
+    }
 
-        for (i, ch) in html_content[start_idx..].char_indices() {
-            if escape_next {
-                escape_next = false;
-                continue;
-            }
+    fn response_cache(&self) -> Option<ResponseCache> {
+        (!self.no_cache)
+            .then(|| ResponseCache::new(&self.cache_dir, Duration::from_secs(self.max_age)))
+    }
 
-            match ch {
-                '\\' => escape_next = true,
-                '"' if !escape_next => in_string = !in_string,
-                '[' if !in_string => bracket_count += 1,
-                ']' if !in_string => {
-                    bracket_count -= 1;
-                    if bracket_count == 0 {
-                        let json_str = &html_content[start_idx..=start_idx + i];
-                        let unescaped = json_str.replace(r#"\""#, r#"""#);
-                        return Ok(unescape_unicode(&unescaped));
-                    }
-                }
-                _ => {}
-            }
+    // Print the banner and set up the scraper
+    fn start_scraper(&self) -> Result<Scraper> {
+        println!("🦀 VivaTech Scraper");
+        println!("━━━━━━━━━━━━━━━━━━━");
+
+        Ok(Scraper::with_timeout(Duration::from_secs(self.timeout))?
+            .with_retry_policy(self.retry_policy())
+            .with_cache(self.response_cache()))
+    }
+}
+
+impl FormatArgs {
+    fn output_options(&self) -> OutputOptions {
+        OutputOptions {
+            format: self.format,
+            null_value: self.null_value.clone(),
+        }
+    }
+
+    // Fail before scraping when the data cannot be written in this format
+    fn check_supports(&self, sessions_or_details: bool) -> Result<()> {
+        if sessions_or_details && !self.format.supports_all_data() {
+            anyhow::bail!(
+                "{} output is only available for speakers and partners, without --with-details",
+                self.format.extension()
+            );
         }
+        Ok(())
     }
 
-    Err(anyhow::anyhow!(
-        "Could not find speaker data JSON in the HTML content"
-    ))
+    // The target's default file name in this format
+    fn output_path(&self, default_csv: &str) -> PathBuf {
+        output::default_output_path(default_csv, self.format)
+    }
+}
+
+impl OutputArgs {
+    fn output_path(&self, default_csv: &str) -> PathBuf {
+        self.output
+            .as_ref()
+            .map_or_else(|| self.format.output_path(default_csv), PathBuf::from)
+    }
+}
+
+impl DetailArgs {
+    fn detail_options(&self, source: &SourceArgs, default_url: &str) -> Option<DetailOptions> {
+        self.with_details.then(|| DetailOptions {
+            base_url: self
+                .details_url
+                .clone()
+                .or_else(|| source.url.clone())
+                .unwrap_or_else(|| default_url.to_string()),
+            concurrency: self.concurrency,
+        })
+    }
+}
+
+impl CategoryArgs {
+    fn filter(&self) -> CategoryFilter {
+        CategoryFilter::from_values(&self.categories)
+    }
+}
+
+impl WebhookArgs {
+    fn notifier(&self, fetch: &FetchArgs) -> Result<Option<Notifier>> {
+        let webhooks: Vec<Webhook> = self
+            .webhook
+            .iter()
+            .map(|url| (url, WebhookKind::Generic))
+            .chain(
+                self.slack_webhook
+                    .iter()
+                    .map(|url| (url, WebhookKind::Slack)),
+            )
+            .map(|(url, kind)| Webhook {
+                url: url.clone(),
+                kind,
+            })
+            .collect();
+        if webhooks.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            Notifier::new(webhooks, Duration::from_secs(fetch.timeout))?
+                .with_template(&self.webhook_template)
+                .with_secret(self.webhook_secret.clone())
+                .with_retry_policy(fetch.retry_policy()),
+        ))
+    }
+}
+
+impl RecordArgs {
+    fn snapshot_options(&self, fetch: &FetchArgs) -> Result<Option<SnapshotOptions>> {
+        let Some(store) = &self.store else {
+            return Ok(None);
+        };
+        Ok(Some(SnapshotOptions {
+            store: SnapshotStore::open(store)?,
+            notifier: self.webhooks.notifier(fetch)?,
+            report: None,
+        }))
+    }
+}
+
+impl StoreArgs {
+    fn open(&self) -> Result<SnapshotStore> {
+        SnapshotStore::open(&self.store)
+    }
+}
+
+impl ScheduleArgs {
+    fn schedule(&self) -> Result<Schedule> {
+        match &self.cron {
+            Some(expression) => Schedule::cron(expression),
+            None => Schedule::interval(Duration::from_secs(self.interval)),
+        }
+    }
 }
 
 // Save HTML for debugging if extraction fails
@@ -249,112 +550,241 @@ fn save_debug_html(html_content: &str, filename: &str) -> Result<()> {
     Ok(())
 }
 
-fn parse_speakers_from_json(json_str: &str) -> Result<Vec<Speaker>> {
-    let speakers: Vec<Speaker> =
-        serde_json::from_str(json_str).context("Failed to parse JSON data into Speaker structs")?;
-
-    log::info!("Successfully parsed {} speakers from JSON", speakers.len());
-    Ok(speakers)
-}
-
-// Convert Speaker structs to CSV-ready format
-fn convert_to_csv_records(speakers: Vec<Speaker>) -> Vec<SpeakerRecord> {
-    speakers
-        .into_iter()
-        .map(|speaker| {
-            let (image_small, image_thumbnail, image_large, image_main) =
-                speaker.image.as_ref().map_or_else(
-                    || {
-                        (
-                            "N/A".to_string(),
-                            "N/A".to_string(),
-                            "N/A".to_string(),
-                            "N/A".to_string(),
-                        )
-                    },
-                    |img| (img.s.clone(), img.t.clone(), img.l.clone(), img.u.clone()),
-                );
-
-            SpeakerRecord {
-                id: speaker.id,
-                first_name: speaker.firstname,
-                last_name: speaker.lastname,
-                email: speaker.email,
-                job_title: speaker.job_title,
-                company: speaker.company,
-                tags: speaker.tags.join(", "),
-                themes: speaker.themes.join(", "),
-                has_bio: speaker.has_bio,
-                has_sessions: speaker.has_sessions,
-                is_official: speaker.is_official,
-                is_partner: speaker.is_partner,
-                is_top_speaker: speaker.top,
-                communication_manager: speaker
-                    .communication_manager
-                    .unwrap_or_else(|| "N/A".to_string()),
-                image_small_url: image_small,
-                image_thumbnail_url: image_thumbnail,
-                image_large_url: image_large,
-                image_main_url: image_main,
+// Load the pages of a source, reporting where they come from
+fn load_pages(scraper: &Scraper, source: &PageSource) -> Result<Vec<Page>> {
+    if source.is_remote() {
+        println!("🌐 Fetching webpage content...");
+    } else {
+        println!("📂 Reading saved HTML pages...");
+    }
+    scraper.load_pages(source)
+}
+
+// Run an extractor over every page, merging the results by key
+// When several pages are given, pages without data are skipped with a warning.
+fn extract_from_pages<T, E, K>(
+    pages: &[Page],
+    source: &PageSource,
+    extractor: E,
+    key: K,
+) -> Result<Vec<T>>
+where
+    E: Fn(&str) -> Result<Vec<T>>,
+    K: Fn(&T) -> String,
+{
+    let mut items = Vec::new();
+    let mut seen = HashSet::new();
+
+    for page in pages {
+        match extractor(&page.html) {
+            Ok(found) => {
+                log::info!("Extracted {} items from {}", found.len(), page.origin);
+                let before = items.len() + found.len();
+                items.extend(found.into_iter().filter(|item| seen.insert(key(item))));
+                if items.len() < before {
+                    log::info!(
+                        "Merged {} items of {} already seen on earlier pages",
+                        before - items.len(),
+                        page.origin
+                    );
+                }
             }
-        })
-        .collect()
+            Err(e) if pages.len() > 1 => log::warn!("Skipping {}: {e}", page.origin),
+            Err(e) => {
+                if source.is_remote() {
+                    save_debug_html(&page.html, "debug_vivatech_page.html")?;
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    if items.is_empty() && pages.len() > 1 {
+        anyhow::bail!("No data found in any of the {} input pages", pages.len());
+    }
+    Ok(items)
 }
 
-fn write_records_to_csv(records: &[SpeakerRecord], output_path: &Path) -> Result<()> {
-    let file = File::create(output_path)
-        .with_context(|| format!("Failed to create CSV file at: {}", output_path.display()))?;
+// How scraped data is written
+struct OutputOptions {
+    format: OutputFormat,
+    null_value: String,
+}
 
-    let mut writer = csv::Writer::from_writer(file);
+// How each scraped type is written in the formats beyond JSON
+trait Export: Serialize + Clone {
+    // CSV goes through the flattened record types
+    fn write_csv(items: &[Self], output_path: &Path, null_value: &str) -> Result<()>;
+    fn write_sqlite(items: &[Self], output_path: &Path) -> Result<()>;
+    fn write_parquet(_items: &[Self], _output_path: &Path) -> Result<()> {
+        anyhow::bail!("Parquet output is only available for speakers and partners")
+    }
+    fn write_xlsx(_items: &[Self], _output_path: &Path) -> Result<()> {
+        anyhow::bail!("Excel output is only available for speakers and partners")
+    }
+}
 
-    for record in records {
-        writer
-            .serialize(record)
-            .context("Failed to write record to CSV")?;
+impl Export for speakers::Speaker {
+    fn write_csv(items: &[Self], output_path: &Path, null_value: &str) -> Result<()> {
+        output::write_csv(
+            &speakers::convert_to_csv_records(items.to_vec()),
+            output_path,
+            null_value,
+        )
+    }
+    fn write_sqlite(items: &[Self], output_path: &Path) -> Result<()> {
+        sqlite::write_speakers(items, output_path)
+    }
+    fn write_parquet(items: &[Self], output_path: &Path) -> Result<()> {
+        parquet::write_speakers(items, output_path)
+    }
+    fn write_xlsx(items: &[Self], output_path: &Path) -> Result<()> {
+        xlsx::write_workbook(items, &[], output_path)
     }
+}
 
-    writer.flush().context("Failed to flush CSV writer")?;
+impl Export for speakers::SpeakerDetails {
+    fn write_csv(items: &[Self], output_path: &Path, null_value: &str) -> Result<()> {
+        output::write_csv(
+            &speakers::convert_to_detail_records(items.to_vec()),
+            output_path,
+            null_value,
+        )
+    }
+    fn write_sqlite(items: &[Self], output_path: &Path) -> Result<()> {
+        sqlite::write_speaker_details(items, output_path)
+    }
+}
 
-    log::info!(
-        "Successfully wrote {} records to CSV file: {}",
-        records.len(),
-        output_path.display()
+impl Export for partners::Partner {
+    fn write_csv(items: &[Self], output_path: &Path, null_value: &str) -> Result<()> {
+        output::write_csv(
+            &partners::convert_to_partner_records(items.to_vec()),
+            output_path,
+            null_value,
+        )
+    }
+    fn write_sqlite(items: &[Self], output_path: &Path) -> Result<()> {
+        sqlite::write_partners(items, output_path)
+    }
+    fn write_parquet(items: &[Self], output_path: &Path) -> Result<()> {
+        parquet::write_partners(items, output_path)
+    }
+    fn write_xlsx(items: &[Self], output_path: &Path) -> Result<()> {
+        xlsx::write_workbook(&[], items, output_path)
+    }
+}
+
+impl Export for partners::PartnerDetails {
+    fn write_csv(items: &[Self], output_path: &Path, null_value: &str) -> Result<()> {
+        output::write_csv(
+            &partners::convert_to_detail_records(items.to_vec()),
+            output_path,
+            null_value,
+        )
+    }
+    fn write_sqlite(items: &[Self], output_path: &Path) -> Result<()> {
+        sqlite::write_partner_details(items, output_path)
+    }
+}
+
+impl Export for sessions::Session {
+    fn write_csv(items: &[Self], output_path: &Path, null_value: &str) -> Result<()> {
+        output::write_csv(
+            &sessions::convert_to_session_records(items.to_vec()),
+            output_path,
+            null_value,
+        )
+    }
+    fn write_sqlite(items: &[Self], output_path: &Path) -> Result<()> {
+        sqlite::write_sessions(items, output_path)
+    }
+}
+
+// Write items in the chosen format
+fn write_output<T: Export>(items: &[T], output_path: &Path, options: &OutputOptions) -> Result<()> {
+    let format = options.format;
+    println!(
+        "💾 Writing data to {} file...",
+        format.extension().to_uppercase()
     );
-    Ok(())
+    match format {
+        OutputFormat::Csv => T::write_csv(items, output_path, &options.null_value),
+        OutputFormat::Json => output::write_json(items, output_path),
+        OutputFormat::Jsonl => output::write_jsonl(items, output_path),
+        OutputFormat::Sqlite => T::write_sqlite(items, output_path),
+        OutputFormat::Parquet => T::write_parquet(items, output_path),
+        OutputFormat::Xlsx => T::write_xlsx(items, output_path),
+    }
 }
 
 // Main scraper logic for speakers
-fn run_scraper(url: &str, output_path: &Path) -> Result<()> {
-    println!("🌐 Fetching webpage content...");
-    let html_content = fetch_page_content(url)?;
+fn run_scraper(
+    scraper: &Scraper,
+    source: &PageSource,
+    output_path: &Path,
+    output_options: &OutputOptions,
+    detail_options: Option<&DetailOptions>,
+    snapshot_options: Option<&SnapshotOptions>,
+) -> Result<()> {
+    let pages = load_pages(scraper, source)?;
 
     println!("🔍 Extracting speaker data from HTML...");
-    let json_str = match extract_json_from_html(&html_content) {
-        Ok(json) => json,
-        Err(e) => {
-            save_debug_html(&html_content, "debug_vivatech_page.html")?;
-            return Err(e);
-        }
-    };
-
-    println!("📊 Parsing JSON data...");
-    let speakers = parse_speakers_from_json(&json_str)?;
+    let speakers = extract_from_pages(
+        &pages,
+        source,
+        speakers::extract_speakers_from_html,
+        |speaker| speaker.id.clone(),
+    )?;
     println!("✅ Found {} speakers", speakers.len());
 
-    let records = convert_to_csv_records(speakers);
+    if let Some(options) = detail_options {
+        println!("🔗 Fetching {} speaker detail pages...", speakers.len());
+        let details =
+            scraper.scrape_speaker_details(&options.base_url, &speakers, options.concurrency);
+        println!("✅ Found details for {} speakers", details.len());
 
-    println!("💾 Writing data to CSV file...");
-    write_records_to_csv(&records, output_path)?;
+        let details_path = output::details_output_path(output_path, output_options.format);
+        write_output(&details, &details_path, output_options)?;
+        println!(
+            "✨ Successfully saved speaker details to: {}",
+            details_path.display()
+        );
+    }
+
+    write_output(&speakers, output_path, output_options)?;
 
     println!(
         "✨ Successfully saved speaker data to: {}",
         output_path.display()
     );
-    Ok(())
+    record_snapshot(snapshot_options, Entity::Speakers, source, &speakers)
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let command = match cli.command {
+        Some(command) => command,
+        // Without a subcommand the scraper scrapes speakers, as it always has
+        None => Command::Speakers(SpeakersArgs::defaults()?),
+    };
+
+    // Fail before scraping when the data cannot be written as asked
+    match &command {
+        Command::Speakers(args) => args
+            .scrape
+            .output
+            .format
+            .check_supports(args.details.with_details)?,
+        Command::Partners(args) => args
+            .scrape
+            .output
+            .format
+            .check_supports(args.details.with_details)?,
+        Command::Sessions(args) => args.output.format.check_supports(true)?,
+        Command::Watch(args) => args.format.check_supports(true)?,
+        _ => {}
+    }
 
     // Set up logging
     let log_level = match cli.verbose {
@@ -369,50 +799,553 @@ fn main() -> Result<()> {
         .format_timestamp(None)
         .init();
 
-    println!("🦀 VivaTech Scraper");
-    println!("━━━━━━━━━━━━━━━━━━━");
-
-    match cli.target {
-        ScrapeTarget::Speakers => {
+    // Reports and listings go to stdout as is, so they skip the banner
+    match command {
+        Command::Speakers(args) => {
+            let scrape = &args.scrape;
+            let snapshot_options = scrape.record.snapshot_options(&scrape.fetch)?;
+            let scraper = scrape.fetch.start_scraper()?;
             println!("🎤 Scraping speakers...");
-            let url = cli.url.as_deref().unwrap_or(TARGET_URL);
-            let output_file = cli
-                .output
-                .unwrap_or_else(|| DEFAULT_OUTPUT_FILE.to_string());
-            let output_path = Path::new(&output_file);
-            run_scraper(url, output_path)?;
+            run_scraper(
+                &scraper,
+                &scrape.source.page_source(speakers::SPEAKERS_URL),
+                &scrape.output.output_path(speakers::DEFAULT_SPEAKERS_OUTPUT),
+                &scrape.output.format.output_options(),
+                args.details
+                    .detail_options(&scrape.source, speakers::SPEAKERS_URL)
+                    .as_ref(),
+                snapshot_options.as_ref(),
+            )
         }
-        ScrapeTarget::Partners => {
+        Command::Partners(args) => {
+            let scrape = &args.scrape;
+            let snapshot_options = scrape.record.snapshot_options(&scrape.fetch)?;
+            let scraper = scrape.fetch.start_scraper()?;
             println!("🤝 Scraping partners...");
-            let url = cli.url.as_deref().unwrap_or(partners::PARTNERS_URL);
-            let output_file = cli
-                .output
-                .unwrap_or_else(|| partners::DEFAULT_PARTNERS_OUTPUT.to_string());
-            let output_path = Path::new(&output_file);
-            run_partners_scraper(url, output_path)?;
+            run_partners_scraper(
+                &scraper,
+                &scrape.source.page_source(partners::PARTNERS_URL),
+                &scrape.output.output_path(partners::DEFAULT_PARTNERS_OUTPUT),
+                &scrape.output.format.output_options(),
+                &args.categories.filter(),
+                args.details
+                    .detail_options(&scrape.source, partners::PARTNERS_URL)
+                    .as_ref(),
+                snapshot_options.as_ref(),
+            )
+        }
+        Command::Sessions(args) => {
+            let snapshot_options = args.record.snapshot_options(&args.fetch)?;
+            let scraper = args.fetch.start_scraper()?;
+            println!("🗓️ Scraping sessions...");
+            run_sessions_scraper(
+                &scraper,
+                &args.source.page_source(sessions::SESSIONS_URL),
+                &args.output.output_path(sessions::DEFAULT_SESSIONS_OUTPUT),
+                &args.output.format.output_options(),
+                snapshot_options.as_ref(),
+            )
+        }
+        Command::Workbook(args) => {
+            let snapshot_options = args.record.snapshot_options(&args.fetch)?;
+            let scraper = args.fetch.start_scraper()?;
+            run_workbook(&args, &scraper, snapshot_options.as_ref())
+        }
+        Command::Categories(args) => {
+            let scraper = args.fetch.start_scraper()?;
+            println!("🏷️ Listing partner categories...");
+            run_categories(&scraper, &args.source.page_source(partners::PARTNERS_URL))
+        }
+        Command::Diff(args) => run_diff(&args),
+        Command::Snapshots(args) => run_snapshots(&args.open()?),
+        Command::History(args) => run_history(&args.store.open()?, &args.record_id),
+        Command::Export(args) => run_export(&args),
+        Command::Watch(args) => {
+            let schedule = args.schedule.schedule()?;
+            let snapshot_options = SnapshotOptions {
+                store: args.store.open()?,
+                notifier: args.webhooks.notifier(&args.fetch)?,
+                report: Some(args.report),
+            };
+            let scraper = args.fetch.start_scraper()?;
+            let watcher = Watcher {
+                args,
+                scraper,
+                snapshot_options,
+            };
+            run_watch(watcher, schedule)
         }
     }
-
-    Ok(())
 }
 
 // Partners scraper wrapper
-fn run_partners_scraper(url: &str, output_path: &Path) -> Result<()> {
-    println!("🌐 Fetching webpage content...");
-    let html_content = fetch_page_content(url)?;
+fn run_partners_scraper(
+    scraper: &Scraper,
+    source: &PageSource,
+    output_path: &Path,
+    output_options: &OutputOptions,
+    filter: &CategoryFilter,
+    detail_options: Option<&DetailOptions>,
+    snapshot_options: Option<&SnapshotOptions>,
+) -> Result<()> {
+    let pages = load_pages(scraper, source)?;
 
     println!("🔍 Extracting partner data from HTML...");
-    let partners = partners::extract_partners_from_html(&html_content)?;
+    let partners = extract_from_pages(
+        &pages,
+        source,
+        |html| partners::extract_partners_with_filter(html, filter),
+        partners::Partner::dedup_key,
+    )?;
     println!("✅ Found {} partners", partners.len());
 
-    let records = partners::convert_to_partner_records(partners);
+    if let Some(options) = detail_options {
+        println!("🔗 Fetching {} partner detail pages...", partners.len());
+        let details =
+            scraper.scrape_partner_details(&options.base_url, &partners, options.concurrency);
+        println!("✅ Found details for {} partners", details.len());
+
+        let details_path = output::details_output_path(output_path, output_options.format);
+        write_output(&details, &details_path, output_options)?;
+        println!(
+            "✨ Successfully saved partner details to: {}",
+            details_path.display()
+        );
+    }
 
-    println!("💾 Writing data to CSV file...");
-    partners::write_partners_to_csv(&records, output_path)?;
+    write_output(&partners, output_path, output_options)?;
 
     println!(
         "✨ Successfully saved partner data to: {}",
         output_path.display()
     );
+    record_snapshot(snapshot_options, Entity::Partners, source, &partners)
+}
+
+// Sessions scraper wrapper
+fn run_sessions_scraper(
+    scraper: &Scraper,
+    source: &PageSource,
+    output_path: &Path,
+    output_options: &OutputOptions,
+    snapshot_options: Option<&SnapshotOptions>,
+) -> Result<()> {
+    let pages = load_pages(scraper, source)?;
+
+    println!("🔍 Extracting session data from HTML...");
+    let sessions = extract_from_pages(
+        &pages,
+        source,
+        sessions::extract_sessions_from_html,
+        |session| session.id.clone(),
+    )?;
+    println!("✅ Found {} sessions", sessions.len());
+
+    write_output(&sessions, output_path, output_options)?;
+
+    println!(
+        "✨ Successfully saved session data to: {}",
+        output_path.display()
+    );
+    record_snapshot(snapshot_options, Entity::Sessions, source, &sessions)
+}
+
+// Scrape speakers and partners into one Excel workbook with a Summary sheet
+fn run_workbook(
+    args: &WorkbookArgs,
+    scraper: &Scraper,
+    snapshot_options: Option<&SnapshotOptions>,
+) -> Result<()> {
+    println!("🎤 Scraping speakers...");
+    let speakers_source = args.sources.page_source(speakers::SPEAKERS_URL);
+    let pages = load_pages(scraper, &speakers_source)?;
+    println!("🔍 Extracting speaker data from HTML...");
+    let speakers = extract_from_pages(
+        &pages,
+        &speakers_source,
+        speakers::extract_speakers_from_html,
+        |speaker| speaker.id.clone(),
+    )?;
+    println!("✅ Found {} speakers", speakers.len());
+
+    println!("🤝 Scraping partners...");
+    let partners_source = args.sources.page_source(partners::PARTNERS_URL);
+    let pages = load_pages(scraper, &partners_source)?;
+    println!("🔍 Extracting partner data from HTML...");
+    let filter = args.categories.filter();
+    let partners = extract_from_pages(
+        &pages,
+        &partners_source,
+        |html| partners::extract_partners_with_filter(html, &filter),
+        partners::Partner::dedup_key,
+    )?;
+    println!("✅ Found {} partners", partners.len());
+
+    let output_path = args.output.as_ref().map_or_else(
+        || PathBuf::from(xlsx::DEFAULT_WORKBOOK_OUTPUT),
+        PathBuf::from,
+    );
+    println!("💾 Writing data to XLSX file...");
+    xlsx::write_workbook(&speakers, &partners, &output_path)?;
+    println!(
+        "✨ Successfully saved speakers and partners to: {}",
+        output_path.display()
+    );
+    record_snapshot(
+        snapshot_options,
+        Entity::Speakers,
+        &speakers_source,
+        &speakers,
+    )?;
+    record_snapshot(
+        snapshot_options,
+        Entity::Partners,
+        &partners_source,
+        &partners,
+    )
+}
+
+// List every partner type found in the pages with its count
+fn run_categories(scraper: &Scraper, source: &PageSource) -> Result<()> {
+    let pages = load_pages(scraper, source)?;
+
+    let mut totals: Vec<(String, usize)> = Vec::new();
+    for page in &pages {
+        let counts = match partners::count_categories(&page.html) {
+            Ok(counts) => counts,
+            Err(e) if pages.len() > 1 => {
+                log::warn!("Skipping {}: {e}", page.origin);
+                continue;
+            }
+            Err(e) => {
+                if source.is_remote() {
+                    save_debug_html(&page.html, "debug_vivatech_page.html")?;
+                }
+                return Err(e);
+            }
+        };
+        for (category, count) in counts {
+            match totals.iter_mut().find(|(known, _)| *known == category) {
+                Some((_, total)) => *total += count,
+                None => totals.push((category, count)),
+            }
+        }
+    }
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    if totals.is_empty() {
+        anyhow::bail!("No partner categories found");
+    }
+
+    let default_filter = CategoryFilter::default();
+    let width = totals
+        .iter()
+        .map(|(category, _)| category.len())
+        .max()
+        .unwrap_or(0);
+    println!("✅ Found {} categories", totals.len());
+    for (category, count) in &totals {
+        let marker = if default_filter.matches(category) {
+            "*"
+        } else {
+            " "
+        };
+        println!("{marker} {category:<width$}  {count}");
+    }
+    println!("(* included by default; pass --category TYPE or --category all to change)");
+    Ok(())
+}
+
+// Compare two exports and print or save the report
+fn run_diff(args: &DiffArgs) -> Result<()> {
+    let old = Snapshot::load(&args.old, &args.null_value)?;
+    let new = Snapshot::load(&args.new, &args.null_value)?;
+
+    let diffs = diff::compare(&old, &new);
+    if diffs.is_empty() {
+        anyhow::bail!("The snapshots share no speakers, partners or sessions to compare");
+    }
+    let rendered = diff::render(&diffs, args.report)?;
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .with_context(|| format!("Failed to write diff report to: {path}"))?;
+            println!("✨ Successfully saved diff report to: {path}");
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+// Add the scraped records to the snapshot store, if one is used, then print
+// and notify the webhooks of what changed since the previous snapshot
+// Called once the records are written, so a failed run records nothing.
+fn record_snapshot<T: Serialize>(
+    snapshot_options: Option<&SnapshotOptions>,
+    entity: Entity,
+    source: &PageSource,
+    records: &[T],
+) -> Result<()> {
+    let Some(SnapshotOptions {
+        store,
+        notifier,
+        report,
+    }) = snapshot_options
+    else {
+        return Ok(());
+    };
+    let previous = store.latest(entity)?;
+    let snapshot = store.record(entity, &source.to_string(), records)?;
+    if previous.as_ref() == Some(&snapshot) {
+        println!("🗄️ Unchanged since snapshot {}", snapshot.id);
+        return Ok(());
+    }
+    println!("🗄️ Recorded snapshot {}", snapshot.id);
+
+    // The first snapshot is the baseline, not a change
+    let Some(previous) = previous.filter(|_| notifier.is_some() || report.is_some()) else {
+        return Ok(());
+    };
+    for entity_diff in diff::compare(&store.load(&previous)?, &store.load(&snapshot)?) {
+        if let Some(report) = report.filter(|_| !entity_diff.is_empty()) {
+            print!(
+                "{}",
+                diff::render(std::slice::from_ref(&entity_diff), report)?
+            );
+        }
+        let Some(notifier) = notifier else {
+            continue;
+        };
+        let event = ChangeEvent {
+            previous: &previous,
+            snapshot: &snapshot,
+            diff: &entity_diff,
+        };
+        // The data is saved either way, so a failed delivery is only reported
+        match notifier.notify(&event) {
+            Ok(true) => println!(
+                "📣 Notified webhooks of {} new and {} updated {}",
+                entity_diff.added.len(),
+                entity_diff.changed.len(),
+                entity.name()
+            ),
+            Ok(false) => log::info!("No new or updated {} to notify", entity.name()),
+            Err(e) => log::warn!("{e:#}"),
+        }
+    }
+    Ok(())
+}
+
+// List the snapshots of the store, oldest first
+fn run_snapshots(store: &SnapshotStore) -> Result<()> {
+    let snapshots = store.list()?;
+    if snapshots.is_empty() {
+        println!("No snapshots recorded yet");
+    }
+    for snapshot in &snapshots {
+        print_snapshot(snapshot);
+    }
+    Ok(())
+}
+
+fn print_snapshot(snapshot: &SnapshotInfo) {
+    println!(
+        "{}  {}  {:>5} {:<8}  {}  {}",
+        snapshot.id,
+        snapshot.taken_at.format("%Y-%m-%d %H:%M:%S"),
+        snapshot.records,
+        snapshot.entity.name(),
+        &snapshot.content_hash[..12],
+        snapshot.source
+    );
+}
+
+// Show in which snapshots a record was added, changed or removed
+fn run_history(store: &SnapshotStore, record_id: &str) -> Result<()> {
+    let history = store.history(record_id)?;
+    if history.is_empty() {
+        anyhow::bail!("Record {record_id:?} is in no snapshot");
+    }
+
+    for entry in &history {
+        let snapshot = &entry.snapshot;
+        let when = snapshot.taken_at.format("%Y-%m-%d %H:%M:%S");
+        match &entry.change {
+            RecordChange::Added => println!("{when}  added    {} ({})", entry.label, snapshot.id),
+            RecordChange::Removed => {
+                println!("{when}  removed  {} ({})", entry.label, snapshot.id)
+            }
+            RecordChange::Changed(changes) => {
+                println!("{when}  changed  {} ({})", entry.label, snapshot.id);
+                for change in changes {
+                    println!("    {}: {:?} -> {:?}", change.field, change.old, change.new);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// Write a past snapshot in the chosen format
+fn run_export(args: &ExportArgs) -> Result<()> {
+    let store = args.store.open()?;
+    let snapshot = store.find(&args.snapshot_id)?;
+    let format = &args.output.format;
+    let output_path = args.output.output.as_ref().map_or_else(
+        || PathBuf::from(format!("{}.{}", snapshot.id, format.format.extension())),
+        PathBuf::from,
+    );
+    let output_options = format.output_options();
+
+    match snapshot.entity {
+        Entity::Speakers => write_output(
+            &store.load_speakers(&snapshot)?,
+            &output_path,
+            &output_options,
+        )?,
+        Entity::Partners => write_output(
+            &store.load_partners(&snapshot)?,
+            &output_path,
+            &output_options,
+        )?,
+        Entity::Sessions => write_output(
+            &store.load_sessions(&snapshot)?,
+            &output_path,
+            &output_options,
+        )?,
+    }
+    println!(
+        "✨ Successfully exported snapshot {} to: {}",
+        snapshot.id,
+        output_path.display()
+    );
     Ok(())
 }
+
+// Long-running state of 'watch', shared with the blocking scrape rounds
+struct Watcher {
+    args: WatchArgs,
+    scraper: Scraper,
+    snapshot_options: SnapshotOptions,
+}
+
+impl Watcher {
+    // Scrape every target, carrying on past the ones that fail
+    fn run_round(&self) {
+        for entity in [Entity::Speakers, Entity::Partners, Entity::Sessions] {
+            if let Err(e) = self.scrape(entity) {
+                log::error!("Scraping {} failed: {e:#}", entity.name());
+            }
+        }
+    }
+
+    // Scrape one target into its default file
+    fn scrape(&self, entity: Entity) -> Result<()> {
+        let WatchArgs {
+            sources, format, ..
+        } = &self.args;
+        let output_options = format.output_options();
+        let snapshot_options = Some(&self.snapshot_options);
+        match entity {
+            Entity::Speakers => {
+                println!("🎤 Scraping speakers...");
+                run_scraper(
+                    &self.scraper,
+                    &sources.page_source(speakers::SPEAKERS_URL),
+                    &format.output_path(speakers::DEFAULT_SPEAKERS_OUTPUT),
+                    &output_options,
+                    None,
+                    snapshot_options,
+                )
+            }
+            Entity::Partners => {
+                println!("🤝 Scraping partners...");
+                run_partners_scraper(
+                    &self.scraper,
+                    &sources.page_source(partners::PARTNERS_URL),
+                    &format.output_path(partners::DEFAULT_PARTNERS_OUTPUT),
+                    &output_options,
+                    &self.args.categories.filter(),
+                    None,
+                    snapshot_options,
+                )
+            }
+            Entity::Sessions => {
+                println!("🗓️ Scraping sessions...");
+                run_sessions_scraper(
+                    &self.scraper,
+                    &sources.page_source(sessions::SESSIONS_URL),
+                    &format.output_path(sessions::DEFAULT_SESSIONS_OUTPUT),
+                    &output_options,
+                    snapshot_options,
+                )
+            }
+        }
+    }
+}
+
+// Scrape on a schedule until SIGTERM or Ctrl-C
+fn run_watch(watcher: Watcher, schedule: Schedule) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start the tokio runtime")?;
+    // Keeping a handle here drops the blocking HTTP clients outside the runtime
+    let watcher = Arc::new(watcher);
+    runtime.block_on(watch(Arc::clone(&watcher), schedule))
+}
+
+async fn watch(watcher: Arc<Watcher>, schedule: Schedule) -> Result<()> {
+    let shutdown = shutdown_signal()?;
+    tokio::pin!(shutdown);
+
+    loop {
+        let started = Utc::now();
+        println!(
+            "⏰ Round started at {}",
+            started.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        let round_watcher = Arc::clone(&watcher);
+        let mut round = tokio::task::spawn_blocking(move || round_watcher.run_round());
+
+        // A round is never cut short, so every file it writes is complete
+        tokio::select! {
+            result = &mut round => result.context("Watch round panicked")?,
+            () = &mut shutdown => {
+                println!("🛑 Stopping after the current round...");
+                round.await.context("Watch round panicked")?;
+                return Ok(());
+            }
+        }
+
+        let next = schedule.next_run(started)?;
+        println!("💤 Next round at {}", next.format("%Y-%m-%d %H:%M:%S UTC"));
+        let wait = (next - Utc::now()).to_std().unwrap_or(Duration::ZERO);
+        tokio::select! {
+            () = tokio::time::sleep(wait) => {}
+            () = &mut shutdown => {
+                println!("🛑 Stopping");
+                return Ok(());
+            }
+        }
+    }
+}
+
+// Resolves on the first SIGTERM or Ctrl-C; handlers are installed right away
+#[cfg(unix)]
+fn shutdown_signal() -> Result<impl std::future::Future<Output = ()>> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate()).context("Failed to listen for SIGTERM")?;
+    Ok(async move {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+    })
+}
+
+#[cfg(not(unix))]
+fn shutdown_signal() -> Result<impl std::future::Future<Output = ()>> {
+    Ok(async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+}
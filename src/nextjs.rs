@@ -0,0 +1,325 @@
+// Next.js page payload parser
+// VivaTech pages are rendered with Next.js, which ships the page data either as
+// React Server Components "flight" chunks pushed through `self.__next_f.push(...)`
+// or as a `__NEXT_DATA__` JSON script. This module reassembles both into JSON trees.
+
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+const FLIGHT_PUSH_NEEDLE: &str = "__next_f.push(";
+const NEXT_DATA_NEEDLE: &str = r#"id="__NEXT_DATA__""#;
+const SCRIPT_END: &str = "</script>";
+
+// Flight chunk types sent by the Next.js runtime
+const CHUNK_BOOTSTRAP: u64 = 0;
+const CHUNK_PAYLOAD: u64 = 1;
+
+// A single row of the flight payload (`<id>:<tag><json>`)
+#[derive(Debug, Clone)]
+pub struct FlightRow {
+    pub id: String,
+    pub tag: Option<String>,
+    pub value: Value,
+}
+
+// Everything Next.js embedded in a page
+#[derive(Debug, Clone, Default)]
+pub struct NextDocument {
+    pub flight: Vec<FlightRow>,
+    pub next_data: Option<Value>,
+    // Byte ranges of the page consumed by the payload scripts
+    pub spans: Vec<Range<usize>>,
+}
+
+impl NextDocument {
+    pub fn is_empty(&self) -> bool {
+        self.flight.is_empty() && self.next_data.is_none()
+    }
+
+    // Reassemble the flight rows into one tree with row references resolved
+    // Returns an array holding every row that no other row references.
+    pub fn flight_tree(&self) -> Option<Value> {
+        let rows: HashMap<&str, &Value> = self
+            .flight
+            .iter()
+            .filter(|row| row.tag.is_none())
+            .map(|row| (row.id.as_str(), &row.value))
+            .collect();
+        if rows.is_empty() {
+            return None;
+        }
+
+        let mut referenced = HashSet::new();
+        for value in rows.values() {
+            collect_references(value, &mut referenced);
+        }
+
+        let mut resolver = Resolver {
+            rows: &rows,
+            resolved: HashMap::new(),
+            stack: Vec::new(),
+        };
+        let mut roots: Vec<&FlightRow> = self
+            .flight
+            .iter()
+            .filter(|row| row.tag.is_none() && !referenced.contains(row.id.as_str()))
+            .collect();
+        // Every row is part of a cycle: fall back to emitting all of them
+        if roots.is_empty() {
+            roots = self.flight.iter().filter(|row| row.tag.is_none()).collect();
+        }
+
+        Some(Value::Array(
+            roots
+                .into_iter()
+                .map(|row| resolver.resolve_row(&row.id))
+                .collect(),
+        ))
+    }
+}
+
+// Parse every Next.js payload embedded in the HTML
+pub fn parse_next_document(html_content: &str) -> NextDocument {
+    let mut document = NextDocument::default();
+
+    let mut payload = String::new();
+    for (span, chunk) in find_flight_chunks(html_content) {
+        document.spans.push(span);
+        match chunk.first().and_then(Value::as_u64) {
+            Some(CHUNK_PAYLOAD) => {
+                if let Some(text) = chunk.get(1).and_then(Value::as_str) {
+                    payload.push_str(text);
+                }
+            }
+            Some(CHUNK_BOOTSTRAP) => {}
+            other => log::debug!("Ignoring flight chunk of type {other:?}"),
+        }
+    }
+    if !payload.is_empty() {
+        document.flight = parse_flight_payload(&payload);
+        log::info!(
+            "Reassembled {} flight rows from {} bytes of RSC payload",
+            document.flight.len(),
+            payload.len()
+        );
+    }
+
+    if let Some((span, value)) = find_next_data(html_content) {
+        log::info!("Found __NEXT_DATA__ payload");
+        document.spans.push(span);
+        document.next_data = Some(value);
+    }
+
+    document
+}
+
+// Locate every `self.__next_f.push([...])` call and parse its argument
+fn find_flight_chunks(html_content: &str) -> Vec<(Range<usize>, Vec<Value>)> {
+    let mut chunks = Vec::new();
+    let mut pos = 0;
+
+    while let Some(idx) = html_content[pos..].find(FLIGHT_PUSH_NEEDLE) {
+        let start = pos + idx;
+        let args_start = start + FLIGHT_PUSH_NEEDLE.len();
+        let mut stream =
+            serde_json::Deserializer::from_str(&html_content[args_start..]).into_iter::<Value>();
+
+        match stream.next() {
+            Some(Ok(Value::Array(chunk))) => {
+                let end = args_start + stream.byte_offset();
+                chunks.push((start..end, chunk));
+                pos = end;
+            }
+            _ => {
+                log::debug!("Skipping unparseable flight chunk at offset {start}");
+                pos = args_start;
+            }
+        }
+    }
+
+    chunks
+}
+
+// Locate the `<script id="__NEXT_DATA__">` payload
+fn find_next_data(html_content: &str) -> Option<(Range<usize>, Value)> {
+    let start = html_content.find(NEXT_DATA_NEEDLE)?;
+    let body_start = start + html_content[start..].find('>')? + 1;
+    let body_end = body_start + html_content[body_start..].find(SCRIPT_END)?;
+
+    match serde_json::from_str(html_content[body_start..body_end].trim()) {
+        Ok(value) => Some((start..body_end, value)),
+        Err(e) => {
+            log::warn!("Failed to parse __NEXT_DATA__ JSON: {e}");
+            None
+        }
+    }
+}
+
+// Split a reassembled flight payload into rows
+// Rows look like `<hex id>:<json>\n`, `<hex id>:<TAG><json>\n` or, for text
+// rows, `<hex id>:T<hex byte length>,<text>` without a trailing newline.
+pub fn parse_flight_payload(payload: &str) -> Vec<FlightRow> {
+    let mut rows = Vec::new();
+    let mut rest = payload;
+
+    while !rest.is_empty() {
+        let Some(colon) = rest.find(':') else {
+            break;
+        };
+        let id = rest[..colon].trim_start_matches('\n');
+        rest = &rest[colon + 1..];
+
+        if !id.is_empty() && id.bytes().all(|b| b.is_ascii_hexdigit()) {
+            if let Some((text, remaining)) = split_text_row(rest) {
+                rows.push(FlightRow {
+                    id: id.to_string(),
+                    tag: None,
+                    value: Value::String(text.to_string()),
+                });
+                rest = remaining;
+                continue;
+            }
+        }
+
+        let line_end = rest.find('\n').unwrap_or(rest.len());
+        let line = &rest[..line_end];
+        rest = rest.get(line_end + 1..).unwrap_or("");
+
+        if id.is_empty() || !id.bytes().all(|b| b.is_ascii_hexdigit()) {
+            log::debug!("Skipping flight row with invalid id {id:?}");
+            continue;
+        }
+
+        let tag_len = line.bytes().take_while(u8::is_ascii_uppercase).count();
+        let (tag, json) = line.split_at(tag_len);
+        match serde_json::from_str(json) {
+            Ok(value) => rows.push(FlightRow {
+                id: id.to_string(),
+                tag: (!tag.is_empty()).then(|| tag.to_string()),
+                value,
+            }),
+            Err(e) => log::debug!("Skipping unparseable flight row {id}: {e}"),
+        }
+    }
+
+    rows
+}
+
+// Split a `T<hex length>,<text>` row into its text and the remaining payload
+fn split_text_row(rest: &str) -> Option<(&str, &str)> {
+    let after_tag = rest.strip_prefix('T')?;
+    let comma = after_tag.find(',')?;
+    let length = usize::from_str_radix(&after_tag[..comma], 16).ok()?;
+    let body = &after_tag[comma + 1..];
+    if length > body.len() || !body.is_char_boundary(length) {
+        return None;
+    }
+    Some(body.split_at(length))
+}
+
+// Parse a `$<id>`, `$L<id>` or `$@<id>` row reference, with an optional `:path`
+fn parse_reference(text: &str) -> Option<(&str, Vec<&str>)> {
+    let rest = text.strip_prefix('$')?;
+    let rest = rest
+        .strip_prefix('L')
+        .or_else(|| rest.strip_prefix('@'))
+        .unwrap_or(rest);
+    let mut parts = rest.split(':');
+    let id = parts.next()?;
+    let is_row_id = !id.is_empty()
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    is_row_id.then(|| (id, parts.collect()))
+}
+
+fn collect_references<'a>(value: &'a Value, referenced: &mut HashSet<&'a str>) {
+    match value {
+        Value::String(text) => {
+            if let Some((id, _)) = parse_reference(text) {
+                referenced.insert(id);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_references(item, referenced);
+            }
+        }
+        Value::Object(obj) => {
+            for item in obj.values() {
+                collect_references(item, referenced);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Replaces row references with the rows they point at
+struct Resolver<'a> {
+    rows: &'a HashMap<&'a str, &'a Value>,
+    resolved: HashMap<String, Value>,
+    stack: Vec<String>,
+}
+
+impl Resolver<'_> {
+    fn resolve_row(&mut self, id: &str) -> Value {
+        if let Some(value) = self.resolved.get(id) {
+            return value.clone();
+        }
+        let Some(row) = self.rows.get(id).copied() else {
+            return Value::Null;
+        };
+
+        self.stack.push(id.to_string());
+        let value = self.resolve(row);
+        self.stack.pop();
+
+        self.resolved.insert(id.to_string(), value.clone());
+        value
+    }
+
+    fn resolve(&mut self, value: &Value) -> Value {
+        match value {
+            Value::String(text) => match parse_reference(text) {
+                Some((id, path))
+                    if self.rows.contains_key(id) && !self.stack.iter().any(|s| s == id) =>
+                {
+                    let target = self.resolve_row(id);
+                    follow_path(target, &path).unwrap_or_else(|| value.clone())
+                }
+                _ => decode_literal(text).map_or_else(|| value.clone(), Value::String),
+            },
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|item| self.resolve(item)).collect())
+            }
+            Value::Object(obj) => Value::Object(
+                obj.iter()
+                    .map(|(key, item)| (key.clone(), self.resolve(item)))
+                    .collect::<Map<String, Value>>(),
+            ),
+            _ => value.clone(),
+        }
+    }
+}
+
+// Undo the encoding of flight strings that are not row references
+// A leading `$` is escaped as `$$`, and dates are sent as `$D<ISO 8601 string>`.
+fn decode_literal(text: &str) -> Option<String> {
+    if let Some(rest) = text.strip_prefix("$$") {
+        return Some(format!("${rest}"));
+    }
+    text.strip_prefix("$D").map(str::to_string)
+}
+
+// Walk a `:`-separated reference path into a resolved row
+fn follow_path(value: Value, path: &[&str]) -> Option<Value> {
+    path.iter().try_fold(value, |current, key| match current {
+        Value::Object(mut obj) => obj.remove(*key),
+        Value::Array(mut items) => {
+            let index = key.parse::<usize>().ok()?;
+            (index < items.len()).then(|| items.swap_remove(index))
+        }
+        _ => None,
+    })
+}
@@ -0,0 +1,126 @@
+// Output formats for scraped data
+// CSV output goes through the flattened record types of each module; the JSON
+// formats serialize the data models directly so arrays, nested objects and
+// booleans keep their native types. SQLite, Parquet and Excel output live in
+// `crate::sqlite`, `crate::parquet` and `crate::xlsx`.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// File format of the scraped data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Csv,
+    // A single pretty-printed JSON array
+    Json,
+    // One JSON object per line
+    Jsonl,
+    // Normalized tables upserted into a SQLite database
+    Sqlite,
+    // Typed columns for analytics tools, speakers and partners only
+    Parquet,
+    // Excel workbook with a sheet per entity, speakers and partners only
+    Xlsx,
+}
+
+impl OutputFormat {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Jsonl => "jsonl",
+            Self::Sqlite => "sqlite",
+            Self::Parquet => "parquet",
+            Self::Xlsx => "xlsx",
+        }
+    }
+
+    // Whether sessions and detail pages can be written in this format
+    pub const fn supports_all_data(self) -> bool {
+        !matches!(self, Self::Parquet | Self::Xlsx)
+    }
+}
+
+// Path of the details companion output
+// A database holds the details in their own tables, other formats get a file.
+pub fn details_output_path(output_path: &Path, format: OutputFormat) -> PathBuf {
+    match format {
+        OutputFormat::Sqlite => output_path.to_path_buf(),
+        _ => crate::details::companion_path(output_path, "details"),
+    }
+}
+
+// Default output path for `format`, swapping the extension of `default_csv`
+pub fn default_output_path(default_csv: &str, format: OutputFormat) -> PathBuf {
+    Path::new(default_csv).with_extension(format.extension())
+}
+
+fn create_file(output_path: &Path) -> Result<BufWriter<File>> {
+    let file = File::create(output_path)
+        .with_context(|| format!("Failed to create output file at: {}", output_path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+// A flattened CSV record of one of the scraped types
+pub trait CsvRecord: Serialize + Clone {
+    // Optional columns spelled out by `--null-value`; ID columns stay empty, so
+    // a missing ID never reads as one
+    fn nullable_fields(&mut self) -> Vec<&mut Option<String>>;
+
+    // Spell out missing values as `null_value` instead of empty fields
+    fn fill_nulls(&mut self, null_value: &str) {
+        for field in self.nullable_fields() {
+            field.get_or_insert_with(|| null_value.to_string());
+        }
+    }
+}
+
+// Write records as CSV, with `null_value` for missing values
+pub fn write_csv<R: CsvRecord>(records: &[R], output_path: &Path, null_value: &str) -> Result<()> {
+    let file = File::create(output_path)
+        .with_context(|| format!("Failed to create CSV file at: {}", output_path.display()))?;
+    let mut writer = csv::Writer::from_writer(file);
+
+    for record in records {
+        let mut record = record.clone();
+        record.fill_nulls(null_value);
+        writer
+            .serialize(record)
+            .context("Failed to write record to CSV")?;
+    }
+
+    writer.flush().context("Failed to flush CSV writer")?;
+
+    log::info!(
+        "Successfully wrote {} records to CSV file: {}",
+        records.len(),
+        output_path.display()
+    );
+    Ok(())
+}
+
+// Write items as a pretty-printed JSON array
+pub fn write_json<T: Serialize>(items: &[T], output_path: &Path) -> Result<()> {
+    let mut writer = create_file(output_path)?;
+    serde_json::to_writer_pretty(&mut writer, items).context("Failed to write JSON output")?;
+    writeln!(writer).context("Failed to write JSON output")?;
+    writer.flush().context("Failed to flush JSON output")?;
+    Ok(())
+}
+
+// Write items as JSON Lines, one object per line
+pub fn write_jsonl<T: Serialize>(items: &[T], output_path: &Path) -> Result<()> {
+    let mut writer = create_file(output_path)?;
+    for item in items {
+        serde_json::to_writer(&mut writer, item).context("Failed to write JSON Lines record")?;
+        writeln!(writer).context("Failed to write JSON Lines record")?;
+    }
+    writer
+        .flush()
+        .context("Failed to flush JSON Lines output")?;
+    Ok(())
+}
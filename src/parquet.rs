@@ -0,0 +1,130 @@
+// Apache Parquet export for VivaTech
+// Columns follow the CSV headers, but keep their real types: flags are
+// booleans, tags and themes are lists of strings, and missing values are nulls.
+
+use ::parquet::arrow::ArrowWriter;
+use ::parquet::basic::Compression;
+use ::parquet::file::properties::WriterProperties;
+use anyhow::{Context, Result};
+use arrow_array::builder::{ListBuilder, StringBuilder};
+use arrow_array::{ArrayRef, BooleanArray, RecordBatch, StringArray};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::partners::Partner;
+use crate::speakers::{Image, Speaker};
+
+// Column name, values and whether the column may hold nulls
+type Column = (&'static str, ArrayRef, bool);
+
+fn required<'a>(name: &'static str, values: impl Iterator<Item = &'a str>) -> Column {
+    (
+        name,
+        Arc::new(values.map(Some).collect::<StringArray>()),
+        false,
+    )
+}
+
+fn optional<'a>(name: &'static str, values: impl Iterator<Item = Option<&'a str>>) -> Column {
+    (name, Arc::new(values.collect::<StringArray>()), true)
+}
+
+fn flags(name: &'static str, values: impl Iterator<Item = bool>) -> Column {
+    (
+        name,
+        Arc::new(values.map(Some).collect::<BooleanArray>()),
+        false,
+    )
+}
+
+fn lists<'a>(name: &'static str, values: impl Iterator<Item = &'a [String]>) -> Column {
+    let mut builder = ListBuilder::new(StringBuilder::new());
+    for list in values {
+        for value in list {
+            builder.values().append_value(value);
+        }
+        builder.append(true);
+    }
+    (name, Arc::new(builder.finish()), false)
+}
+
+fn write_columns(columns: Vec<Column>, output_path: &Path) -> Result<()> {
+    let batch = RecordBatch::try_from_iter_with_nullable(columns)
+        .context("Failed to build Parquet record batch")?;
+    let file = File::create(output_path).with_context(|| {
+        format!(
+            "Failed to create Parquet file at: {}",
+            output_path.display()
+        )
+    })?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties))
+        .context("Failed to create Parquet writer")?;
+    writer
+        .write(&batch)
+        .context("Failed to write Parquet rows")?;
+    writer.close().context("Failed to finish Parquet file")?;
+    Ok(())
+}
+
+pub fn write_speakers(speakers: &[Speaker], output_path: &Path) -> Result<()> {
+    let image_url = |field: fn(&Image) -> Option<&str>| {
+        speakers
+            .iter()
+            .map(move |speaker| speaker.image.as_ref().and_then(field))
+    };
+
+    write_columns(
+        vec![
+            required("ID", speakers.iter().map(|s| s.id.as_str())),
+            required("FirstName", speakers.iter().map(|s| s.firstname.as_str())),
+            required("LastName", speakers.iter().map(|s| s.lastname.as_str())),
+            optional("Email", speakers.iter().map(|s| s.email.as_deref())),
+            required("JobTitle", speakers.iter().map(|s| s.job_title.as_str())),
+            required("Company", speakers.iter().map(|s| s.company.as_str())),
+            lists("Tags", speakers.iter().map(|s| s.tags.as_slice())),
+            lists("Themes", speakers.iter().map(|s| s.themes.as_slice())),
+            flags("HasBio", speakers.iter().map(|s| s.has_bio)),
+            flags("HasSessions", speakers.iter().map(|s| s.has_sessions)),
+            flags("IsOfficial", speakers.iter().map(|s| s.is_official)),
+            flags("IsPartner", speakers.iter().map(|s| s.is_partner)),
+            flags("IsTopSpeaker", speakers.iter().map(|s| s.top)),
+            optional(
+                "CommunicationManager",
+                speakers.iter().map(|s| s.communication_manager.as_deref()),
+            ),
+            optional("ImageSmallURL", image_url(|image| image.s.as_deref())),
+            optional("ImageThumbnailURL", image_url(|image| image.t.as_deref())),
+            optional("ImageLargeURL", image_url(|image| image.l.as_deref())),
+            optional(
+                "ImageMainURL",
+                image_url(|image| Some(image.u.as_str()).filter(|u| !u.is_empty())),
+            ),
+        ],
+        output_path,
+    )
+}
+
+pub fn write_partners(partners: &[Partner], output_path: &Path) -> Result<()> {
+    let optional_field =
+        |name, field: fn(&Partner) -> Option<&str>| optional(name, partners.iter().map(field));
+
+    write_columns(
+        vec![
+            optional_field("ID", |p| p.id.as_deref()),
+            required("CompanyName", partners.iter().map(|p| p.name.as_str())),
+            required("Category", partners.iter().map(|p| p.category.as_str())),
+            optional_field("Country", |p| p.country.as_deref()),
+            optional_field("CountryCode", |p| p.country_code.as_deref()),
+            optional_field("Region", |p| p.region.as_deref()),
+            optional_field("Description", |p| p.description.as_deref()),
+            optional_field("Website", |p| p.website.as_deref()),
+            optional_field("LogoURL", |p| p.logo_url.as_deref()),
+        ],
+        output_path,
+    )
+}
@@ -2,8 +2,14 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::fs::File;
-use std::path::Path;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+use crate::cities::{self, CityResolution};
+use crate::countries::{self, Country};
+use crate::details;
+use crate::extract::{self, first_name, first_scalar, first_string, name_list};
+use crate::output::CsvRecord;
 
 // Constants
 pub const PARTNERS_URL: &str = "https://vivatechnology.com/partners";
@@ -12,126 +18,294 @@ pub const DEFAULT_PARTNERS_OUTPUT: &str = "vivatech_partners_2025.csv";
 // Partner data model
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Partner {
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub id: Option<String>,
     pub name: String,
     #[serde(default)]
     pub category: String,
     #[serde(default)]
-    pub country: String,
+    pub country: Option<String>,
+    #[serde(default)]
+    pub country_code: Option<String>,
+    #[serde(default)]
+    pub region: Option<String>,
     #[serde(default)]
-    pub description: String,
+    pub description: Option<String>,
     #[serde(default)]
-    pub website: String,
+    pub website: Option<String>,
     #[serde(default)]
-    pub logo_url: String,
+    pub logo_url: Option<String>,
+}
+
+impl Partner {
+    // Key identifying the same partner across listings
+    // The source ID is stable; partners without one fall back to their name.
+    pub fn dedup_key(&self) -> String {
+        match &self.id {
+            Some(id) => id.clone(),
+            None => format!("name:{}", self.name),
+        }
+    }
 }
 
 // CSV output format
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PartnerRecord {
+    #[serde(rename = "ID", default)]
+    pub id: Option<String>,
     #[serde(rename = "CompanyName")]
-    company_name: String,
+    pub company_name: String,
     #[serde(rename = "Category")]
-    category: String,
+    pub category: String,
     #[serde(rename = "Country")]
-    country: String,
+    pub country: Option<String>,
+    #[serde(rename = "CountryCode", default)]
+    pub country_code: Option<String>,
+    #[serde(rename = "Region", default)]
+    pub region: Option<String>,
     #[serde(rename = "Description")]
-    description: String,
+    pub description: Option<String>,
     #[serde(rename = "Website")]
-    website: String,
+    pub website: Option<String>,
     #[serde(rename = "LogoURL")]
-    logo_url: String,
+    pub logo_url: Option<String>,
 }
 
-// Extract partner data from HTML - looks for JSON array
-pub fn extract_partners_from_html(html_content: &str) -> Result<Vec<Partner>> {
-    if let Some(start_idx) = html_content.find(r#"[{\"id\":\""#) {
-        let mut bracket_count = 0;
-        let mut in_string = false;
-        let mut escape_next = false;
-
-        for (i, ch) in html_content[start_idx..].char_indices() {
-            if escape_next {
-                escape_next = false;
-                continue;
-            }
+// Field names used for a partner's location
+const COUNTRY_KEYS: [&str; 5] = [
+    "country",
+    "countryCode",
+    "country_code",
+    "countryName",
+    "headquarters_country",
+];
+const CITY_KEYS: [&str; 3] = ["city", "headquarters", "location"];
 
-            match ch {
-                '\\' => escape_next = true,
-                '"' if !escape_next => in_string = !in_string,
-                '[' if !in_string => bracket_count += 1,
-                ']' if !in_string => {
-                    bracket_count -= 1;
-                    if bracket_count == 0 {
-                        let json_str = &html_content[start_idx..=start_idx + i];
-                        let unescaped = json_str.replace(r#"\""#, r#"""#);
-                        let final_json = unescape_unicode(&unescaped);
-
-                        if let Ok(json_value) =
-                            serde_json::from_str::<serde_json::Value>(&final_json)
-                        {
-                            if let Some(array) = json_value.as_array() {
-                                return Ok(extract_partners_from_json_array(array));
-                            }
-                        }
-                        break;
-                    }
-                }
-                _ => {}
-            }
+// Field names used by partner detail pages
+const FIGURE_KEYS: [&str; 3] = ["key_figures", "keyFigures", "figures"];
+const BOOTH_KEYS: [&str; 5] = [
+    "booth",
+    "boothNumber",
+    "booth_number",
+    "stand",
+    "standNumber",
+];
+const HALL_KEYS: [&str; 3] = ["hall", "hallName", "hall_name"];
+const SECTOR_KEYS: [&str; 4] = ["sectors", "industries", "sector", "industry"];
+const FUNDING_KEYS: [&str; 4] = ["fundingStage", "funding_stage", "funding", "fundraising"];
+const EMPLOYEE_KEYS: [&str; 5] = [
+    "employees",
+    "employeeCount",
+    "employee_count",
+    "nb_employees",
+    "headcount",
+];
+const FOUNDED_KEYS: [&str; 5] = [
+    "foundingYear",
+    "founding_year",
+    "founded",
+    "yearFounded",
+    "creation_year",
+];
+const TEAM_KEYS: [&str; 4] = ["team", "teamMembers", "members", "contacts"];
+const JOB_TITLE_KEYS: [&str; 4] = ["jobTitle", "job_title", "position", "role"];
+
+// Exhibitor staff listed on a partner's detail page
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TeamMember {
+    pub name: String,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub job_title: Option<String>,
+}
+
+// Full exhibitor profile from the partner's detail page
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PartnerDetails {
+    pub id: String,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub booth: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub hall: Option<String>,
+    #[serde(default)]
+    pub sectors: Vec<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub funding_stage: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub employee_count: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub founding_year: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub linkedin_url: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub twitter_url: Option<String>,
+    #[serde(default)]
+    pub team_members: Vec<TeamMember>,
+}
+
+// CSV output format of the partner details companion file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PartnerDetailRecord {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Booth")]
+    pub booth: Option<String>,
+    #[serde(rename = "Hall")]
+    pub hall: Option<String>,
+    #[serde(rename = "Sectors")]
+    pub sectors: String,
+    #[serde(rename = "FundingStage")]
+    pub funding_stage: Option<String>,
+    #[serde(rename = "Employees")]
+    pub employee_count: Option<String>,
+    #[serde(rename = "FoundingYear")]
+    pub founding_year: Option<String>,
+    #[serde(rename = "LinkedInURL")]
+    pub linkedin_url: Option<String>,
+    #[serde(rename = "XURL")]
+    pub twitter_url: Option<String>,
+    #[serde(rename = "TeamMembers")]
+    pub team_members: String,
+}
+
+// Category value selecting every partner type
+pub const ALL_CATEGORIES: &str = "all";
+
+// Which partner types (the `type` field) are extracted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CategoryFilter {
+    // Sponsoring partners and startups
+    #[default]
+    PartnersAndStartups,
+    // Every exhibitor type, including investors, media and institutions
+    All,
+    // Only the listed types, compared case-insensitively
+    Only(Vec<String>),
+}
 
-            if i > 50_000_000 {
-                break;
-            } // Safety limit
+impl CategoryFilter {
+    // Build a filter from `--category` values, where `all` selects everything
+    pub fn from_values(values: &[String]) -> Self {
+        if values.is_empty() {
+            Self::default()
+        } else if values
+            .iter()
+            .any(|value| value.trim().eq_ignore_ascii_case(ALL_CATEGORIES))
+        {
+            Self::All
+        } else {
+            Self::Only(
+                values
+                    .iter()
+                    .map(|value| value.trim().to_string())
+                    .collect(),
+            )
         }
     }
 
-    Err(anyhow::anyhow!("No partner data found"))
+    pub fn matches(&self, category: &str) -> bool {
+        match self {
+            Self::PartnersAndStartups => category.contains("partner") || category == "startup",
+            Self::All => true,
+            Self::Only(categories) => categories
+                .iter()
+                .any(|wanted| wanted.eq_ignore_ascii_case(category.trim())),
+        }
+    }
+}
+
+// Check whether an embedded JSON object looks like a partner/exhibitor
+pub fn is_partner_object(obj: &Map<String, Value>) -> bool {
+    obj.get("name").is_some_and(Value::is_string) && obj.get("type").is_some_and(Value::is_string)
+}
+
+// Extract partner data from the embedded JSON in the HTML
+pub fn extract_partners_from_html(html_content: &str) -> Result<Vec<Partner>> {
+    extract_partners_with_filter(html_content, &CategoryFilter::default())
+}
+
+// Extract the partners whose type is selected by `filter`
+pub fn extract_partners_with_filter(
+    html_content: &str,
+    filter: &CategoryFilter,
+) -> Result<Vec<Partner>> {
+    let documents = extract::find_embedded_json(html_content);
+    extract::find_object_array(&documents, is_partner_object)
+        .map(|array| extract_partners_from_json_array(array, filter))
+        .ok_or_else(|| anyhow::anyhow!("No partner data found"))
+}
+
+// Count every partner `type` value in the page, most frequent first
+pub fn count_categories(html_content: &str) -> Result<Vec<(String, usize)>> {
+    let documents = extract::find_embedded_json(html_content);
+    let array = extract::find_object_array(&documents, is_partner_object)
+        .ok_or_else(|| anyhow::anyhow!("No partner data found"))?;
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for category in array
+        .iter()
+        .filter_map(|item| item.get("type").and_then(Value::as_str))
+    {
+        *counts.entry(category).or_default() += 1;
+    }
+
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(category, count)| (category.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(counts)
+}
+
+// Owned copy of a string field, or `None` when missing or empty
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.filter(|value| !value.is_empty()).map(str::to_string)
 }
 
 // Extract partners from parsed JSON array
-fn extract_partners_from_json_array(array: &[serde_json::Value]) -> Vec<Partner> {
+fn extract_partners_from_json_array(array: &[Value], filter: &CategoryFilter) -> Vec<Partner> {
     let mut partners = Vec::new();
+    let mut seen = HashSet::new();
+    let mut duplicates = 0;
 
     for item in array {
         if let Some(obj) = item.as_object() {
             // Check if this looks like a partner/exhibitor object
             if let (Some(name_val), Some(type_val)) = (obj.get("name"), obj.get("type")) {
                 if let (Some(name), Some(type_str)) = (name_val.as_str(), type_val.as_str()) {
-                    // Only include partners and startups
-                    if type_str.contains("partner") || type_str == "startup" {
+                    // Only include the selected partner types
+                    if filter.matches(type_str) {
+                        let location = resolve_partner_location(obj, name);
+                        let country = location.country;
                         let partner = Partner {
+                            id: obj.get("id").and_then(extract::id_string),
                             name: name.to_string(),
                             category: type_str.to_string(),
-                            country: obj
-                                .get("key_figures")
-                                .and_then(|kf| kf.get("city"))
-                                .and_then(|c| c.as_str())
-                                .map_or_else(
-                                    || extract_country_from_name(name),
-                                    |city| extract_country_from_city(city).to_string(),
-                                ),
-                            description: obj
-                                .get("desc")
-                                .or_else(|| obj.get("short_desc"))
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            website: obj
-                                .get("website")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            logo_url: obj
-                                .get("logo")
-                                .and_then(|logo| logo.get("u"))
-                                .and_then(|u| u.as_str())
-                                .unwrap_or("")
-                                .to_string(),
+                            country: country.map(|c| c.name.to_string()),
+                            country_code: country.map(|c| c.alpha2.to_string()),
+                            region: location.region,
+                            description: non_empty(
+                                obj.get("desc")
+                                    .or_else(|| obj.get("short_desc"))
+                                    .and_then(|v| v.as_str()),
+                            ),
+                            website: non_empty(obj.get("website").and_then(|v| v.as_str())),
+                            logo_url: non_empty(
+                                obj.get("logo")
+                                    .and_then(|logo| logo.get("u"))
+                                    .and_then(|u| u.as_str()),
+                            ),
                         };
 
                         // Avoid duplicates
-                        if !partners.iter().any(|p: &Partner| p.name == partner.name) {
+                        if seen.insert(partner.dedup_key()) {
                             partners.push(partner);
+                        } else {
+                            log::info!(
+                                "Merged duplicate partner entry {} ({})",
+                                partner.dedup_key(),
+                                partner.name
+                            );
+                            duplicates += 1;
                         }
                     }
                 }
@@ -139,119 +313,242 @@ fn extract_partners_from_json_array(array: &[serde_json::Value]) -> Vec<Partner>
         }
     }
 
+    if duplicates > 0 {
+        log::info!("Merged {duplicates} duplicate partner entries");
+    }
     log::info!("Extracted {} partners from JSON array", partners.len());
     partners
 }
 
-// Map common cities to countries
-fn extract_country_from_city(city: &str) -> &str {
-    match city {
-        s if s.contains("Paris") => "France",
-        s if s.contains("London") => "UK",
-        s if s.contains("Berlin") => "Germany",
-        s if s.contains("Tokyo") => "Japan",
-        s if s.contains("New York") || s.contains("San Francisco") => "USA",
-        s if s.contains("Beijing") || s.contains("Shanghai") => "China",
-        s if s.contains("Mumbai") || s.contains("Bangalore") => "India",
-        s if s.contains("Toronto") || s.contains("Montreal") => "Canada",
-        _ => "",
+// Extract a partner's profile from its detail page
+pub fn extract_partner_details_from_html(html_content: &str, id: &str) -> Result<PartnerDetails> {
+    let documents = extract::find_embedded_json(html_content);
+    let partner_objects = extract::find_objects_by_id(&documents, id);
+    if partner_objects.is_empty() {
+        anyhow::bail!("Could not find details of partner {id} in the HTML content");
     }
+
+    // Key figures and booth objects carry most of the profile fields
+    let booth_objects: Vec<&Map<String, Value>> = partner_objects
+        .iter()
+        .flat_map(|obj| BOOTH_KEYS.iter().filter_map(|key| obj.get(*key)))
+        .filter_map(Value::as_object)
+        .collect();
+    let mut objects = partner_objects.clone();
+    objects.extend(
+        partner_objects
+            .iter()
+            .flat_map(|obj| FIGURE_KEYS.iter().filter_map(|key| obj.get(*key)))
+            .filter_map(Value::as_object),
+    );
+    objects.extend(&booth_objects);
+    let find = |keys: &[&str]| objects.iter().find_map(|obj| first_scalar(obj, keys));
+
+    let links = details::find_social_links(&partner_objects);
+
+    Ok(PartnerDetails {
+        id: id.to_string(),
+        booth: find(&BOOTH_KEYS).or_else(|| {
+            booth_objects
+                .iter()
+                .find_map(|obj| first_scalar(obj, &["number", "code", "name"]))
+        }),
+        hall: objects
+            .iter()
+            .find_map(|obj| first_name(obj, &HALL_KEYS).or_else(|| first_scalar(obj, &HALL_KEYS))),
+        sectors: objects
+            .iter()
+            .flat_map(|obj| SECTOR_KEYS.iter().filter_map(|key| obj.get(*key)))
+            .map(|value| match value {
+                Value::Array(_) => name_list(value),
+                _ => extract::display_name(value).into_iter().collect(),
+            })
+            .find(|sectors| !sectors.is_empty())
+            .unwrap_or_default(),
+        funding_stage: objects
+            .iter()
+            .find_map(|obj| first_name(obj, &FUNDING_KEYS)),
+        employee_count: find(&EMPLOYEE_KEYS),
+        founding_year: find(&FOUNDED_KEYS).and_then(|founded| find_year(&founded)),
+        linkedin_url: links.linkedin,
+        twitter_url: links.twitter,
+        team_members: partner_objects
+            .iter()
+            .flat_map(|obj| TEAM_KEYS.iter().filter_map(|key| obj.get(*key)))
+            .filter_map(Value::as_array)
+            .map(|items| items.iter().filter_map(team_member).collect::<Vec<_>>())
+            .find(|members| !members.is_empty())
+            .unwrap_or_default(),
+    })
+}
+
+// Team member given as a name or as a person object
+fn team_member(value: &Value) -> Option<TeamMember> {
+    let obj = match value {
+        Value::String(name) if !name.trim().is_empty() => {
+            return Some(TeamMember {
+                name: name.trim().to_string(),
+                job_title: None,
+            })
+        }
+        Value::Object(obj) => obj,
+        _ => return None,
+    };
+
+    let full_name = [["firstname", "firstName"], ["lastname", "lastName"]]
+        .iter()
+        .filter_map(|keys| first_string(obj, keys))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let name = first_string(obj, &["name", "fullName", "full_name"])
+        .or_else(|| (!full_name.is_empty()).then_some(full_name))?;
+
+    Some(TeamMember {
+        name,
+        job_title: first_string(obj, &JOB_TITLE_KEYS),
+    })
+}
+
+// Four-digit year at the start of a year or date value
+fn find_year(text: &str) -> Option<String> {
+    let digits: String = text.chars().take_while(char::is_ascii_digit).collect();
+    (digits.len() == 4).then_some(digits)
 }
 
-// Try to extract country from company name (e.g., "Company - France")
-fn extract_country_from_name(name: &str) -> String {
-    if let Some(dash_pos) = name.rfind(" - ") {
-        let potential_country = name[dash_pos + 3..].trim();
-        if is_likely_country(potential_country) {
-            return potential_country.to_string();
+// Country named by a "Company - Country" suffix of the name
+// Other words of the name are never matched, so "Jordan Ventures" or "Georgia
+// Labs" are not placed in a country.
+fn extract_country_from_name(name: &str) -> Option<Country> {
+    let (_, suffix) = name.rsplit_once(" - ")?;
+    countries::resolve(suffix)
+}
+
+// Where a partner is based
+#[derive(Debug, Default)]
+struct Location {
+    country: Option<Country>,
+    region: Option<String>,
+}
+
+// Resolve a partner's location from its structured fields, then from heuristics
+// Cities shared by several places are reported and left unresolved.
+fn resolve_partner_location(obj: &Map<String, Value>, name: &str) -> Location {
+    let figures: Vec<&Map<String, Value>> = std::iter::once(obj)
+        .chain(
+            FIGURE_KEYS
+                .iter()
+                .filter_map(|key| obj.get(*key).and_then(Value::as_object)),
+        )
+        .collect();
+
+    // Structured country fields hold a name or an ISO code
+    let structured = figures.iter().find_map(|fields| {
+        first_name(fields, &COUNTRY_KEYS).and_then(|country| {
+            countries::by_code(&country).or_else(|| countries::resolve(&country))
+        })
+    });
+    let Some(city) = figures
+        .iter()
+        .find_map(|fields| first_string(fields, &CITY_KEYS))
+    else {
+        return Location {
+            country: structured.or_else(|| extract_country_from_name(name)),
+            region: None,
+        };
+    };
+
+    let (mut candidates, dominant) = match cities::resolve(&city) {
+        CityResolution::Found(found) => (vec![found], None),
+        CityResolution::Dominant {
+            city: dominant,
+            mut homonyms,
+        } => {
+            homonyms.insert(0, dominant);
+            (homonyms, Some(dominant))
         }
+        CityResolution::Ambiguous(candidates) => (candidates, None),
+        CityResolution::Unknown => (Vec::new(), None),
+    };
+    if let Some(country) = structured {
+        candidates.retain(|candidate| candidate.country == country.alpha2);
+    } else if let (Some(dominant), Some(next)) = (dominant, candidates.get(1)) {
+        // Without a country to tell them apart, the far larger place wins
+        log::info!(
+            "City {city:?} of partner {name} taken as {} ({}, {}), {} times as populous as {} ({}, {})",
+            dominant.name,
+            dominant.region,
+            dominant.country,
+            dominant.population / next.population.max(1),
+            next.name,
+            next.region,
+            next.country
+        );
+        candidates = vec![dominant];
     }
 
-    // Check for common country names in company name
-    let countries = [
-        ("France", "France"),
-        ("USA", "USA"),
-        ("United States", "USA"),
-        ("UK", "UK"),
-        ("United Kingdom", "UK"),
-        ("Germany", "Germany"),
-        ("Japan", "Japan"),
-        ("China", "China"),
-        ("India", "India"),
-        ("Canada", "Canada"),
-    ];
-
-    let name_upper = name.to_uppercase();
-    for (pattern, country) in &countries {
-        if name_upper.contains(&pattern.to_uppercase()) {
-            return (*country).to_string();
+    match candidates.as_slice() {
+        [found] => Location {
+            country: structured.or_else(|| countries::by_code(found.country)),
+            region: non_empty(Some(found.region)),
+        },
+        [] => Location {
+            // Unknown cities may still name their country, as in "Smallville, France"
+            country: structured
+                .or_else(|| {
+                    city.rsplit_once(',')
+                        .and_then(|(_, country)| countries::resolve(country))
+                })
+                .or_else(|| countries::find_in_text(&city))
+                .or_else(|| extract_country_from_name(name)),
+            region: None,
+        },
+        _ => {
+            let places: Vec<String> = candidates
+                .iter()
+                .map(|candidate| {
+                    format!(
+                        "{} ({}, {})",
+                        candidate.name, candidate.region, candidate.country
+                    )
+                })
+                .collect();
+            log::warn!(
+                "Ambiguous city {city:?} for partner {name}: could be {}",
+                places.join(", ")
+            );
+            Location {
+                country: structured,
+                region: None,
+            }
         }
     }
+}
 
-    String::new()
+impl CsvRecord for PartnerRecord {
+    fn nullable_fields(&mut self) -> Vec<&mut Option<String>> {
+        vec![
+            &mut self.country,
+            &mut self.country_code,
+            &mut self.region,
+            &mut self.description,
+            &mut self.website,
+            &mut self.logo_url,
+        ]
+    }
 }
 
-// Check if text is likely a country name
-fn is_likely_country(text: &str) -> bool {
-    let countries = [
-        "France",
-        "USA",
-        "United States",
-        "UK",
-        "United Kingdom",
-        "Germany",
-        "Japan",
-        "China",
-        "India",
-        "Canada",
-        "Spain",
-        "Italy",
-        "Netherlands",
-        "Belgium",
-        "Switzerland",
-        "Austria",
-        "Australia",
-        "New Zealand",
-        "Singapore",
-        "Korea",
-        "Brazil",
-        "Mexico",
-        "Argentina",
-        "Chile",
-        "Poland",
-        "Czech Republic",
-        "Hungary",
-        "Romania",
-        "Greece",
-        "Portugal",
-        "Ireland",
-        "Scotland",
-        "Wales",
-        "Sweden",
-        "Norway",
-        "Denmark",
-        "Finland",
-        "Russia",
-        "Ukraine",
-        "Turkey",
-        "Israel",
-        "UAE",
-        "Saudi Arabia",
-        "Egypt",
-        "South Africa",
-        "Nigeria",
-        "Kenya",
-        "Morocco",
-        "Algeria",
-        "Tunisia",
-        "Albania",
-        "Armenia",
-        "Bangladesh",
-    ];
-
-    countries
-        .iter()
-        .any(|&country| text.eq_ignore_ascii_case(country))
+impl CsvRecord for PartnerDetailRecord {
+    fn nullable_fields(&mut self) -> Vec<&mut Option<String>> {
+        vec![
+            &mut self.booth,
+            &mut self.hall,
+            &mut self.funding_stage,
+            &mut self.employee_count,
+            &mut self.founding_year,
+            &mut self.linkedin_url,
+            &mut self.twitter_url,
+        ]
+    }
 }
 
 // Convert to CSV format
@@ -259,9 +556,12 @@ pub fn convert_to_partner_records(partners: Vec<Partner>) -> Vec<PartnerRecord>
     partners
         .into_iter()
         .map(|partner| PartnerRecord {
+            id: partner.id,
             company_name: partner.name,
             category: partner.category,
             country: partner.country,
+            country_code: partner.country_code,
+            region: partner.region,
             description: partner.description,
             website: partner.website,
             logo_url: partner.logo_url,
@@ -269,60 +569,29 @@ pub fn convert_to_partner_records(partners: Vec<Partner>) -> Vec<PartnerRecord>
         .collect()
 }
 
-// Write to CSV file
-pub fn write_partners_to_csv(records: &[PartnerRecord], output_path: &Path) -> Result<()> {
-    let file = File::create(output_path)?;
-    let mut writer = csv::Writer::from_writer(file);
-
-    for record in records {
-        writer.serialize(record)?;
-    }
-
-    writer.flush()?;
-    Ok(())
-}
-
-// Unescape Unicode sequences
-fn unescape_unicode(input: &str) -> String {
-    let mut result = String::new();
-    let mut chars = input.chars();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\\' {
-            if let Some(next_ch) = chars.next() {
-                match next_ch {
-                    'u' => {
-                        let hex_chars: String = chars.by_ref().take(4).collect();
-                        if hex_chars.len() == 4 {
-                            if let Ok(code_point) = u32::from_str_radix(&hex_chars, 16) {
-                                if let Some(unicode_char) = char::from_u32(code_point) {
-                                    result.push(unicode_char);
-                                    continue;
-                                }
-                            }
-                        }
-                        // If parsing failed, add the original sequence
-                        result.push('\\');
-                        result.push('u');
-                        result.push_str(&hex_chars);
-                    }
-                    'n' => result.push('\n'),
-                    'r' => result.push('\r'),
-                    't' => result.push('\t'),
-                    '"' => result.push('"'),
-                    '\\' => result.push('\\'),
-                    _ => {
-                        result.push('\\');
-                        result.push(next_ch);
-                    }
-                }
-            } else {
-                result.push(ch);
-            }
-        } else {
-            result.push(ch);
-        }
-    }
-
-    result
+// Convert partner details to the companion CSV format
+pub fn convert_to_detail_records(details: Vec<PartnerDetails>) -> Vec<PartnerDetailRecord> {
+    details
+        .into_iter()
+        .map(|detail| PartnerDetailRecord {
+            id: detail.id,
+            booth: detail.booth,
+            hall: detail.hall,
+            sectors: detail.sectors.join(", "),
+            funding_stage: detail.funding_stage,
+            employee_count: detail.employee_count,
+            founding_year: detail.founding_year,
+            linkedin_url: detail.linkedin_url,
+            twitter_url: detail.twitter_url,
+            team_members: detail
+                .team_members
+                .iter()
+                .map(|member| match &member.job_title {
+                    Some(job_title) => format!("{} ({job_title})", member.name),
+                    None => member.name.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join("; "),
+        })
+        .collect()
 }
@@ -0,0 +1,43 @@
+// Run schedules for watch mode
+// Rounds either start a fixed interval apart or follow a cron expression
+// (five fields, or six with seconds first), evaluated in UTC. A round that
+// overruns its slot is followed by the next one right away.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use croner::Cron;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    Interval(Duration),
+    Cron(Box<Cron>),
+}
+
+impl Schedule {
+    pub fn interval(interval: Duration) -> Result<Self> {
+        anyhow::ensure!(!interval.is_zero(), "The watch interval must be positive");
+        Ok(Self::Interval(interval))
+    }
+
+    pub fn cron(expression: &str) -> Result<Self> {
+        expression
+            .parse()
+            .map(|cron| Self::Cron(Box::new(cron)))
+            .with_context(|| format!("Invalid cron expression: {expression:?}"))
+    }
+
+    // Start of the round following one started at `last_start`
+    pub fn next_run(&self, last_start: DateTime<Utc>) -> Result<DateTime<Utc>> {
+        match self {
+            Self::Interval(interval) => {
+                let interval =
+                    chrono::Duration::from_std(*interval).context("Watch interval is too long")?;
+                Ok(last_start + interval)
+            }
+            Self::Cron(cron) => cron
+                .find_next_occurrence(&last_start, false)
+                .context("The cron expression never matches again"),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// High-level scraping entry point combining fetching and extraction
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::cache::ResponseCache;
+use crate::details;
+use crate::fetch::{self, RetryPolicy};
+use crate::partners::{self, Partner, PartnerDetails};
+use crate::sessions::{self, Session};
+use crate::speakers::{self, Speaker, SpeakerDetails};
+
+// File extensions picked up when reading a directory of saved pages
+const HTML_EXTENSIONS: [&str; 2] = ["html", "htm"];
+
+// Where the page HTML comes from
+#[derive(Debug, Clone)]
+pub enum PageSource {
+    // Fetch the live page over HTTP
+    Url(String),
+    // Read a saved HTML file
+    File(PathBuf),
+    // Read every saved HTML file in a directory
+    Directory(PathBuf),
+}
+
+impl PageSource {
+    pub const fn is_remote(&self) -> bool {
+        matches!(self, Self::Url(_))
+    }
+}
+
+impl std::fmt::Display for PageSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Url(url) => f.write_str(url),
+            Self::File(path) | Self::Directory(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+// A loaded page together with where it came from
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub origin: String,
+    pub html: String,
+}
+
+// Reusable scraper holding a configured HTTP client
+#[derive(Debug, Clone)]
+pub struct Scraper {
+    client: Client,
+    retry_policy: RetryPolicy,
+    cache: Option<ResponseCache>,
+}
+
+impl Scraper {
+    pub fn new() -> Result<Self> {
+        Self::with_timeout(fetch::DEFAULT_TIMEOUT)
+    }
+
+    // Create a scraper whose requests give up after `timeout`
+    pub fn with_timeout(timeout: Duration) -> Result<Self> {
+        Ok(Self {
+            client: fetch::build_client(timeout)?,
+            retry_policy: RetryPolicy::default(),
+            cache: None,
+        })
+    }
+
+    // Replace the policy used to retry failed fetches
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    // Cache responses on disk and revalidate them with conditional requests
+    #[must_use]
+    pub fn with_cache(mut self, cache: Option<ResponseCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    // Fetch the raw HTML of a page
+    pub fn fetch_page_content(&self, url: &str) -> Result<String> {
+        fetch::fetch_page_content(&self.client, url, &self.retry_policy, self.cache.as_ref())
+    }
+
+    // Load every page of a source, fetching or reading from disk as needed
+    pub fn load_pages(&self, source: &PageSource) -> Result<Vec<Page>> {
+        match source {
+            PageSource::Url(url) => Ok(vec![Page {
+                origin: url.clone(),
+                html: self.fetch_page_content(url)?,
+            }]),
+            PageSource::File(path) => Ok(vec![read_page_file(path)?]),
+            PageSource::Directory(dir) => read_page_dir(dir),
+        }
+    }
+
+    // Fetch a speakers page and extract every speaker from it
+    pub fn scrape_speakers(&self, url: &str) -> Result<Vec<Speaker>> {
+        let html_content = self.fetch_page_content(url)?;
+        speakers::extract_speakers_from_html(&html_content)
+    }
+
+    // Fetch a partners page and extract every partner from it
+    pub fn scrape_partners(&self, url: &str) -> Result<Vec<Partner>> {
+        let html_content = self.fetch_page_content(url)?;
+        partners::extract_partners_from_html(&html_content)
+    }
+
+    // Follow each speaker's detail page listed under `base_url`
+    // Pages that fail to load or parse are logged and skipped.
+    pub fn scrape_speaker_details(
+        &self,
+        base_url: &str,
+        speakers: &[Speaker],
+        concurrency: usize,
+    ) -> Vec<SpeakerDetails> {
+        let ids: Vec<&str> = speakers.iter().map(|speaker| speaker.id.as_str()).collect();
+        self.scrape_details(
+            "speaker",
+            base_url,
+            &ids,
+            concurrency,
+            speakers::extract_speaker_details_from_html,
+        )
+    }
+
+    // Follow each partner's detail page listed under `base_url`
+    // Partners without an ID have no detail page and are skipped.
+    pub fn scrape_partner_details(
+        &self,
+        base_url: &str,
+        partners: &[Partner],
+        concurrency: usize,
+    ) -> Vec<PartnerDetails> {
+        let ids: Vec<&str> = partners
+            .iter()
+            .filter_map(|partner| partner.id.as_deref())
+            .collect();
+        self.scrape_details(
+            "partner",
+            base_url,
+            &ids,
+            concurrency,
+            partners::extract_partner_details_from_html,
+        )
+    }
+
+    fn scrape_details<T, F>(
+        &self,
+        kind: &str,
+        base_url: &str,
+        ids: &[&str],
+        concurrency: usize,
+        extract: F,
+    ) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&str, &str) -> Result<T> + Sync,
+    {
+        let pages: Vec<(String, String)> = ids
+            .iter()
+            .map(|id| ((*id).to_string(), details::detail_url(base_url, id)))
+            .collect();
+
+        details::crawl(self, &pages, concurrency, extract)
+            .into_iter()
+            .filter_map(|(id, result)| match result {
+                Ok(details) => Some(details),
+                Err(e) => {
+                    log::warn!("Skipping details of {kind} {id}: {e:#}");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // Fetch the agenda page and extract every session from it
+    pub fn scrape_sessions(&self, url: &str) -> Result<Vec<Session>> {
+        let html_content = self.fetch_page_content(url)?;
+        sessions::extract_sessions_from_html(&html_content)
+    }
+}
+
+// Read a saved HTML page from disk
+pub fn read_page_file(path: &Path) -> Result<Page> {
+    let html = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read HTML file: {}", path.display()))?;
+
+    log::info!("Read {} bytes from {}", html.len(), path.display());
+    Ok(Page {
+        origin: path.display().to_string(),
+        html,
+    })
+}
+
+// Read every saved HTML page in a directory, in file name order
+pub fn read_page_dir(dir: &Path) -> Result<Vec<Page>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)
+        .with_context(|| format!("Failed to read input directory: {}", dir.display()))?
+    {
+        let path = entry
+            .with_context(|| format!("Failed to list input directory: {}", dir.display()))?
+            .path();
+        let is_html = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| HTML_EXTENSIONS.iter().any(|e| ext.eq_ignore_ascii_case(e)));
+        if path.is_file() && is_html {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    if paths.is_empty() {
+        anyhow::bail!("No HTML files found in input directory: {}", dir.display());
+    }
+
+    paths.iter().map(|path| read_page_file(path)).collect()
+}
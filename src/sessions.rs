@@ -0,0 +1,186 @@
+// Session/agenda scraping module for VivaTech
+
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::extract::{self, first_name, first_string, id_list, id_string, name_list};
+use crate::output::CsvRecord;
+
+// Constants
+pub const SESSIONS_URL: &str = "https://vivatechnology.com/agenda";
+pub const DEFAULT_SESSIONS_OUTPUT: &str = "vivatech_sessions_2025.csv";
+// Times without an offset are local to the venue in Paris
+pub const DEFAULT_TIMEZONE: Tz = chrono_tz::Europe::Paris;
+
+// Field names used by the agenda JSON for each session attribute
+const START_KEYS: [&str; 4] = ["startDate", "start", "startTime", "beginDate"];
+const END_KEYS: [&str; 3] = ["endDate", "end", "endTime"];
+const TIMEZONE_KEYS: [&str; 2] = ["timezone", "timeZone"];
+const DESCRIPTION_KEYS: [&str; 3] = ["description", "desc", "shortDescription"];
+const STAGE_KEYS: [&str; 1] = ["stage"];
+const ROOM_KEYS: [&str; 3] = ["room", "place", "location"];
+const FORMAT_KEYS: [&str; 3] = ["format", "type", "sessionType"];
+
+// Session data model
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Session {
+    pub id: String,
+    pub title: String,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub stage: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub room: Option<String>,
+    pub start_time: Option<DateTime<FixedOffset>>,
+    pub end_time: Option<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub timezone: String,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub themes: Vec<String>,
+    #[serde(default)]
+    pub speaker_ids: Vec<String>,
+}
+
+// CSV output format
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionRecord {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Title")]
+    pub title: String,
+    #[serde(rename = "Description")]
+    pub description: Option<String>,
+    #[serde(rename = "Stage")]
+    pub stage: Option<String>,
+    #[serde(rename = "Room")]
+    pub room: Option<String>,
+    #[serde(rename = "StartTime")]
+    pub start_time: Option<String>,
+    #[serde(rename = "EndTime")]
+    pub end_time: Option<String>,
+    #[serde(rename = "Timezone")]
+    pub timezone: String,
+    #[serde(rename = "Format")]
+    pub format: Option<String>,
+    #[serde(rename = "Themes")]
+    pub themes: String,
+    #[serde(rename = "SpeakerIDs")]
+    pub speaker_ids: String,
+}
+
+// Check whether an embedded JSON object looks like an agenda session
+pub fn is_session_object(obj: &Map<String, Value>) -> bool {
+    obj.contains_key("id")
+        && obj.get("title").is_some_and(Value::is_string)
+        && START_KEYS.iter().any(|key| obj.contains_key(*key))
+}
+
+// Extract session data from the embedded JSON in the HTML
+pub fn extract_sessions_from_html(html_content: &str) -> Result<Vec<Session>> {
+    let documents = extract::find_embedded_json(html_content);
+    let array = extract::find_object_array(&documents, is_session_object)
+        .ok_or_else(|| anyhow::anyhow!("Could not find session data JSON in the HTML content"))?;
+
+    let sessions: Vec<Session> = array
+        .iter()
+        .filter_map(Value::as_object)
+        .filter(|obj| is_session_object(obj))
+        .filter_map(session_from_object)
+        .collect();
+
+    log::info!("Extracted {} sessions from JSON array", sessions.len());
+    Ok(sessions)
+}
+
+fn session_from_object(obj: &Map<String, Value>) -> Option<Session> {
+    let id = id_string(obj.get("id")?)?;
+    let title = obj.get("title")?.as_str()?.trim().to_string();
+
+    let timezone_name = first_string(obj, &TIMEZONE_KEYS);
+    let timezone = timezone_name
+        .as_deref()
+        .and_then(|name| name.parse::<Tz>().ok())
+        .unwrap_or(DEFAULT_TIMEZONE);
+
+    let start_time = first_string(obj, &START_KEYS).and_then(|s| parse_time(&s, timezone));
+    let end_time = first_string(obj, &END_KEYS).and_then(|s| parse_time(&s, timezone));
+
+    Some(Session {
+        id,
+        title,
+        description: first_string(obj, &DESCRIPTION_KEYS),
+        stage: first_name(obj, &STAGE_KEYS),
+        room: first_name(obj, &ROOM_KEYS),
+        start_time,
+        end_time,
+        timezone: timezone.name().to_string(),
+        format: first_name(obj, &FORMAT_KEYS),
+        themes: obj.get("themes").map(name_list).unwrap_or_default(),
+        speaker_ids: obj
+            .get("speakers")
+            .or_else(|| obj.get("speakerIds"))
+            .map(id_list)
+            .unwrap_or_default(),
+    })
+}
+
+// Parse a session time, localising times without an offset to `timezone`
+fn parse_time(text: &str, timezone: Tz) -> Option<DateTime<FixedOffset>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Some(time);
+    }
+
+    let naive = [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok());
+    match naive.map(|naive| timezone.from_local_datetime(&naive).earliest()) {
+        Some(Some(local)) => Some(local.fixed_offset()),
+        _ => {
+            log::warn!("Could not parse session time: {text}");
+            None
+        }
+    }
+}
+
+impl CsvRecord for SessionRecord {
+    fn nullable_fields(&mut self) -> Vec<&mut Option<String>> {
+        vec![
+            &mut self.description,
+            &mut self.stage,
+            &mut self.room,
+            &mut self.start_time,
+            &mut self.end_time,
+            &mut self.format,
+        ]
+    }
+}
+
+// Convert to CSV format
+pub fn convert_to_session_records(sessions: Vec<Session>) -> Vec<SessionRecord> {
+    sessions
+        .into_iter()
+        .map(|session| SessionRecord {
+            id: session.id,
+            title: session.title,
+            description: session.description,
+            stage: session.stage,
+            room: session.room,
+            start_time: session.start_time.map(|t| t.to_rfc3339()),
+            end_time: session.end_time.map(|t| t.to_rfc3339()),
+            timezone: session.timezone,
+            format: session.format,
+            themes: session.themes.join(", "),
+            speaker_ids: session.speaker_ids.join(", "),
+        })
+        .collect()
+}
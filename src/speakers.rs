@@ -0,0 +1,276 @@
+// Speaker scraping module for VivaTech
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::details;
+use crate::extract::{self, first_name, first_string, id_list};
+use crate::output::CsvRecord;
+
+// Constants
+pub const SPEAKERS_URL: &str = "https://vivatechnology.com/speakers";
+pub const DEFAULT_SPEAKERS_OUTPUT: &str = "vivatech_speakers_2025_extended.csv";
+
+// Speaker data model matching JSON structure
+// The site's camelCase names are read as aliases, so JSON output uses the
+// same snake_case keys as partners and sessions.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Speaker {
+    pub id: String,
+    pub firstname: String,
+    pub lastname: String,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub email: Option<String>,
+    #[serde(alias = "jobTitle")]
+    pub job_title: String,
+    pub company: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub themes: Vec<String>,
+    pub image: Option<Image>,
+    #[serde(alias = "hasBio", default)]
+    pub has_bio: bool,
+    #[serde(alias = "hasSessions", default)]
+    pub has_sessions: bool,
+    #[serde(alias = "isOfficial", default)]
+    pub is_official: bool,
+    #[serde(alias = "isPartner", default)]
+    pub is_partner: bool,
+    #[serde(default)]
+    pub top: bool,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub communication_manager: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Image {
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub s: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub t: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub l: Option<String>,
+    pub u: String,
+}
+
+// CSV output format
+// Missing values are `None`; the CSV writer leaves them empty.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct SpeakerRecord {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "FirstName")]
+    pub first_name: String,
+    #[serde(rename = "LastName")]
+    pub last_name: String,
+    #[serde(rename = "Email")]
+    pub email: Option<String>,
+    #[serde(rename = "JobTitle")]
+    pub job_title: String,
+    #[serde(rename = "Company")]
+    pub company: String,
+    #[serde(rename = "Tags")]
+    pub tags: String,
+    #[serde(rename = "Themes")]
+    pub themes: String,
+    #[serde(rename = "HasBio")]
+    pub has_bio: bool,
+    #[serde(rename = "HasSessions")]
+    pub has_sessions: bool,
+    #[serde(rename = "IsOfficial")]
+    pub is_official: bool,
+    #[serde(rename = "IsPartner")]
+    pub is_partner: bool,
+    #[serde(rename = "IsTopSpeaker")]
+    pub is_top_speaker: bool,
+    #[serde(rename = "CommunicationManager")]
+    pub communication_manager: Option<String>,
+    #[serde(rename = "ImageSmallURL")]
+    pub image_small_url: Option<String>,
+    #[serde(rename = "ImageThumbnailURL")]
+    pub image_thumbnail_url: Option<String>,
+    #[serde(rename = "ImageLargeURL")]
+    pub image_large_url: Option<String>,
+    #[serde(rename = "ImageMainURL")]
+    pub image_main_url: Option<String>,
+}
+
+// Field names used by speaker detail pages
+const BIOGRAPHY_KEYS: [&str; 3] = ["biography", "bio", "description"];
+const NATIONALITY_KEYS: [&str; 1] = ["nationality"];
+const SESSION_KEYS: [&str; 2] = ["sessions", "sessionIds"];
+
+// Full speaker profile from the speaker's detail page
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SpeakerDetails {
+    pub id: String,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub biography: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub linkedin_url: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub twitter_url: Option<String>,
+    #[serde(default, deserialize_with = "extract::non_empty_string")]
+    pub nationality: Option<String>,
+    #[serde(default)]
+    pub session_ids: Vec<String>,
+}
+
+// CSV output format of the speaker details companion file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SpeakerDetailRecord {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Biography")]
+    pub biography: Option<String>,
+    #[serde(rename = "LinkedInURL")]
+    pub linkedin_url: Option<String>,
+    #[serde(rename = "XURL")]
+    pub twitter_url: Option<String>,
+    #[serde(rename = "Nationality")]
+    pub nationality: Option<String>,
+    #[serde(rename = "SessionIDs")]
+    pub session_ids: String,
+}
+
+impl CsvRecord for SpeakerDetailRecord {
+    fn nullable_fields(&mut self) -> Vec<&mut Option<String>> {
+        vec![
+            &mut self.biography,
+            &mut self.linkedin_url,
+            &mut self.twitter_url,
+            &mut self.nationality,
+        ]
+    }
+}
+
+// Check whether an embedded JSON object looks like a speaker
+pub fn is_speaker_object(obj: &Map<String, Value>) -> bool {
+    obj.contains_key("id") && obj.contains_key("firstname") && obj.contains_key("lastname")
+}
+
+pub fn parse_speakers_from_json(json_str: &str) -> Result<Vec<Speaker>> {
+    let speakers: Vec<Speaker> =
+        serde_json::from_str(json_str).context("Failed to parse JSON data into Speaker structs")?;
+
+    log::info!("Successfully parsed {} speakers from JSON", speakers.len());
+    Ok(speakers)
+}
+
+// Extract and parse every speaker embedded in the HTML
+pub fn extract_speakers_from_html(html_content: &str) -> Result<Vec<Speaker>> {
+    let documents = extract::find_embedded_json(html_content);
+    let array = extract::find_object_array(&documents, is_speaker_object)
+        .ok_or_else(|| anyhow::anyhow!("Could not find speaker data JSON in the HTML content"))?;
+
+    let speakers: Vec<Speaker> = array
+        .iter()
+        .filter(|item| item.as_object().is_some_and(is_speaker_object))
+        .filter_map(|item| match Speaker::deserialize(item) {
+            Ok(speaker) => Some(speaker),
+            Err(e) => {
+                log::warn!("Skipping malformed speaker entry: {e}");
+                None
+            }
+        })
+        .collect();
+
+    log::info!("Successfully parsed {} speakers from HTML", speakers.len());
+    Ok(speakers)
+}
+
+// Extract the details of speaker `id` from their detail page
+pub fn extract_speaker_details_from_html(html_content: &str, id: &str) -> Result<SpeakerDetails> {
+    let documents = extract::find_embedded_json(html_content);
+    let objects = extract::find_objects_by_id(&documents, id);
+    if objects.is_empty() {
+        anyhow::bail!("Could not find details of speaker {id} in the HTML content");
+    }
+
+    let links = details::find_social_links(&objects);
+    Ok(SpeakerDetails {
+        id: id.to_string(),
+        biography: objects
+            .iter()
+            .find_map(|obj| first_string(obj, &BIOGRAPHY_KEYS))
+            .map(|bio| details::html_to_text(&bio))
+            .filter(|bio| !bio.is_empty()),
+        linkedin_url: links.linkedin,
+        twitter_url: links.twitter,
+        nationality: objects
+            .iter()
+            .find_map(|obj| first_name(obj, &NATIONALITY_KEYS)),
+        session_ids: objects
+            .iter()
+            .flat_map(|obj| SESSION_KEYS.iter().filter_map(|key| obj.get(*key)))
+            .map(id_list)
+            .find(|ids| !ids.is_empty())
+            .unwrap_or_default(),
+    })
+}
+
+// Convert speaker details to the companion CSV format
+pub fn convert_to_detail_records(details: Vec<SpeakerDetails>) -> Vec<SpeakerDetailRecord> {
+    details
+        .into_iter()
+        .map(|detail| SpeakerDetailRecord {
+            id: detail.id,
+            biography: detail.biography,
+            linkedin_url: detail.linkedin_url,
+            twitter_url: detail.twitter_url,
+            nationality: detail.nationality,
+            session_ids: detail.session_ids.join(", "),
+        })
+        .collect()
+}
+
+// Convert Speaker structs to CSV-ready format
+pub fn convert_to_csv_records(speakers: Vec<Speaker>) -> Vec<SpeakerRecord> {
+    speakers
+        .into_iter()
+        .map(|speaker| {
+            let (image_small, image_thumbnail, image_large, image_main) =
+                speaker.image.map_or((None, None, None, None), |img| {
+                    (img.s, img.t, img.l, Some(img.u).filter(|u| !u.is_empty()))
+                });
+
+            SpeakerRecord {
+                id: speaker.id,
+                first_name: speaker.firstname,
+                last_name: speaker.lastname,
+                email: speaker.email,
+                job_title: speaker.job_title,
+                company: speaker.company,
+                tags: speaker.tags.join(", "),
+                themes: speaker.themes.join(", "),
+                has_bio: speaker.has_bio,
+                has_sessions: speaker.has_sessions,
+                is_official: speaker.is_official,
+                is_partner: speaker.is_partner,
+                is_top_speaker: speaker.top,
+                communication_manager: speaker.communication_manager,
+                image_small_url: image_small,
+                image_thumbnail_url: image_thumbnail,
+                image_large_url: image_large,
+                image_main_url: image_main,
+            }
+        })
+        .collect()
+}
+
+impl CsvRecord for SpeakerRecord {
+    fn nullable_fields(&mut self) -> Vec<&mut Option<String>> {
+        vec![
+            &mut self.email,
+            &mut self.communication_manager,
+            &mut self.image_small_url,
+            &mut self.image_thumbnail_url,
+            &mut self.image_large_url,
+            &mut self.image_main_url,
+        ]
+    }
+}
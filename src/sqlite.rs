@@ -0,0 +1,629 @@
+// SQLite export for VivaTech
+// Scraped data goes into normalized tables keyed by the source IDs. Rows are
+// upserted, so repeated runs keep one database up to date instead of
+// replacing it, and list fields such as tags are rewritten per parent row.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OpenFlags, Transaction};
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::partners::{Partner, PartnerDetails};
+use crate::sessions::Session;
+use crate::speakers::{Image, Speaker, SpeakerDetails};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS speakers (
+    id TEXT PRIMARY KEY,
+    firstname TEXT NOT NULL,
+    lastname TEXT NOT NULL,
+    email TEXT,
+    job_title TEXT NOT NULL,
+    company TEXT NOT NULL,
+    has_bio INTEGER NOT NULL,
+    has_sessions INTEGER NOT NULL,
+    is_official INTEGER NOT NULL,
+    is_partner INTEGER NOT NULL,
+    is_top_speaker INTEGER NOT NULL,
+    communication_manager TEXT
+);
+CREATE INDEX IF NOT EXISTS speakers_name ON speakers (lastname, firstname);
+CREATE INDEX IF NOT EXISTS speakers_company ON speakers (company);
+
+CREATE TABLE IF NOT EXISTS images (
+    speaker_id TEXT PRIMARY KEY REFERENCES speakers (id) ON DELETE CASCADE,
+    small_url TEXT,
+    thumbnail_url TEXT,
+    large_url TEXT,
+    main_url TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS tags (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS themes (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS speaker_tags (
+    speaker_id TEXT NOT NULL REFERENCES speakers (id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tags (id),
+    PRIMARY KEY (speaker_id, tag_id)
+);
+CREATE INDEX IF NOT EXISTS speaker_tags_tag ON speaker_tags (tag_id);
+
+CREATE TABLE IF NOT EXISTS speaker_themes (
+    speaker_id TEXT NOT NULL REFERENCES speakers (id) ON DELETE CASCADE,
+    theme_id INTEGER NOT NULL REFERENCES themes (id),
+    PRIMARY KEY (speaker_id, theme_id)
+);
+CREATE INDEX IF NOT EXISTS speaker_themes_theme ON speaker_themes (theme_id);
+
+CREATE TABLE IF NOT EXISTS speaker_details (
+    speaker_id TEXT PRIMARY KEY,
+    biography TEXT,
+    linkedin_url TEXT,
+    twitter_url TEXT,
+    nationality TEXT
+);
+
+-- The few partners without a source ID are matched by name, so the table has
+-- a surrogate key of its own
+CREATE TABLE IF NOT EXISTS partners (
+    partner_key INTEGER PRIMARY KEY,
+    id TEXT UNIQUE,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    country TEXT,
+    country_code TEXT,
+    region TEXT,
+    description TEXT,
+    website TEXT,
+    logo_url TEXT
+);
+CREATE UNIQUE INDEX IF NOT EXISTS partners_without_id ON partners (name) WHERE id IS NULL;
+CREATE INDEX IF NOT EXISTS partners_category ON partners (category);
+CREATE INDEX IF NOT EXISTS partners_country ON partners (country_code);
+
+CREATE TABLE IF NOT EXISTS partner_details (
+    partner_id TEXT PRIMARY KEY,
+    booth TEXT,
+    hall TEXT,
+    funding_stage TEXT,
+    employee_count TEXT,
+    founding_year TEXT,
+    linkedin_url TEXT,
+    twitter_url TEXT
+);
+
+CREATE TABLE IF NOT EXISTS partner_sectors (
+    partner_id TEXT NOT NULL,
+    sector TEXT NOT NULL,
+    PRIMARY KEY (partner_id, sector)
+);
+CREATE INDEX IF NOT EXISTS partner_sectors_sector ON partner_sectors (sector);
+
+CREATE TABLE IF NOT EXISTS team_members (
+    partner_id TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    job_title TEXT,
+    PRIMARY KEY (partner_id, position)
+);
+
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT,
+    stage TEXT,
+    room TEXT,
+    start_time TEXT,
+    end_time TEXT,
+    timezone TEXT NOT NULL,
+    format TEXT
+);
+CREATE INDEX IF NOT EXISTS sessions_start ON sessions (start_time);
+
+CREATE TABLE IF NOT EXISTS session_themes (
+    session_id TEXT NOT NULL REFERENCES sessions (id) ON DELETE CASCADE,
+    theme_id INTEGER NOT NULL REFERENCES themes (id),
+    PRIMARY KEY (session_id, theme_id)
+);
+CREATE INDEX IF NOT EXISTS session_themes_theme ON session_themes (theme_id);
+
+-- Speakers may live in another database, so this is not a foreign key
+CREATE TABLE IF NOT EXISTS session_speakers (
+    session_id TEXT NOT NULL REFERENCES sessions (id) ON DELETE CASCADE,
+    speaker_id TEXT NOT NULL,
+    PRIMARY KEY (session_id, speaker_id)
+);
+CREATE INDEX IF NOT EXISTS session_speakers_speaker ON session_speakers (speaker_id);
+";
+
+// Open (or create) the database at `path` and make sure the schema exists
+pub fn open(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path)
+        .with_context(|| format!("Failed to open SQLite database at: {}", path.display()))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .context("Failed to enable foreign keys")?;
+    conn.execute_batch(SCHEMA)
+        .context("Failed to create SQLite schema")?;
+    Ok(conn)
+}
+
+// Run `write` in a single transaction on the database at `path`
+fn with_transaction<F>(path: &Path, write: F) -> Result<()>
+where
+    F: FnOnce(&Transaction) -> rusqlite::Result<()>,
+{
+    let mut conn = open(path)?;
+    let tx = conn
+        .transaction()
+        .context("Failed to start SQLite transaction")?;
+    write(&tx).context("Failed to write rows to SQLite")?;
+    tx.commit().context("Failed to commit SQLite transaction")
+}
+
+// Id of the tag or theme `name` in `table`, inserting it when new
+fn label_id(tx: &Transaction, table: &str, name: &str) -> rusqlite::Result<i64> {
+    tx.execute(
+        &format!("INSERT INTO {table} (name) VALUES (?1) ON CONFLICT (name) DO NOTHING"),
+        [name],
+    )?;
+    tx.query_row(
+        &format!("SELECT id FROM {table} WHERE name = ?1"),
+        [name],
+        |row| row.get(0),
+    )
+}
+
+// Replace the links from `owner_id` to the labels `names`
+fn link_labels(
+    tx: &Transaction,
+    link_table: &str,
+    owner_column: &str,
+    label_table: &str,
+    owner_id: &str,
+    names: &[String],
+) -> rusqlite::Result<()> {
+    // "tags" are linked through "tag_id", "themes" through "theme_id"
+    let label_column = format!("{}_id", label_table.trim_end_matches('s'));
+    tx.execute(
+        &format!("DELETE FROM {link_table} WHERE {owner_column} = ?1"),
+        [owner_id],
+    )?;
+    for name in names {
+        let label = label_id(tx, label_table, name)?;
+        tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO {link_table} ({owner_column}, {label_column}) VALUES (?1, ?2)"
+            ),
+            params![owner_id, label],
+        )?;
+    }
+    Ok(())
+}
+
+// Upsert speakers with their images, tags and themes
+pub fn write_speakers(speakers: &[Speaker], path: &Path) -> Result<()> {
+    with_transaction(path, |tx| {
+        for speaker in speakers {
+            tx.execute(
+                "INSERT INTO speakers (id, firstname, lastname, email, job_title, company,
+                     has_bio, has_sessions, is_official, is_partner, is_top_speaker,
+                     communication_manager)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                 ON CONFLICT (id) DO UPDATE SET
+                     firstname = excluded.firstname,
+                     lastname = excluded.lastname,
+                     email = excluded.email,
+                     job_title = excluded.job_title,
+                     company = excluded.company,
+                     has_bio = excluded.has_bio,
+                     has_sessions = excluded.has_sessions,
+                     is_official = excluded.is_official,
+                     is_partner = excluded.is_partner,
+                     is_top_speaker = excluded.is_top_speaker,
+                     communication_manager = excluded.communication_manager",
+                params![
+                    speaker.id,
+                    speaker.firstname,
+                    speaker.lastname,
+                    speaker.email,
+                    speaker.job_title,
+                    speaker.company,
+                    speaker.has_bio,
+                    speaker.has_sessions,
+                    speaker.is_official,
+                    speaker.is_partner,
+                    speaker.top,
+                    speaker.communication_manager,
+                ],
+            )?;
+
+            match &speaker.image {
+                Some(image) => {
+                    tx.execute(
+                        "INSERT INTO images (speaker_id, small_url, thumbnail_url, large_url, main_url)
+                         VALUES (?1, ?2, ?3, ?4, ?5)
+                         ON CONFLICT (speaker_id) DO UPDATE SET
+                             small_url = excluded.small_url,
+                             thumbnail_url = excluded.thumbnail_url,
+                             large_url = excluded.large_url,
+                             main_url = excluded.main_url",
+                        params![speaker.id, image.s, image.t, image.l, image.u],
+                    )?;
+                }
+                None => {
+                    tx.execute("DELETE FROM images WHERE speaker_id = ?1", [&speaker.id])?;
+                }
+            }
+
+            link_labels(
+                tx,
+                "speaker_tags",
+                "speaker_id",
+                "tags",
+                &speaker.id,
+                &speaker.tags,
+            )?;
+            link_labels(
+                tx,
+                "speaker_themes",
+                "speaker_id",
+                "themes",
+                &speaker.id,
+                &speaker.themes,
+            )?;
+        }
+        Ok(())
+    })
+}
+
+// Upsert speaker detail pages
+pub fn write_speaker_details(details: &[SpeakerDetails], path: &Path) -> Result<()> {
+    with_transaction(path, |tx| {
+        for detail in details {
+            tx.execute(
+                "INSERT INTO speaker_details (speaker_id, biography, linkedin_url, twitter_url, nationality)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (speaker_id) DO UPDATE SET
+                     biography = excluded.biography,
+                     linkedin_url = excluded.linkedin_url,
+                     twitter_url = excluded.twitter_url,
+                     nationality = excluded.nationality",
+                params![
+                    detail.id,
+                    detail.biography,
+                    detail.linkedin_url,
+                    detail.twitter_url,
+                    detail.nationality,
+                ],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+// Upsert partners, keyed by ID (or by name for entries without one)
+// A partner that gains an ID takes over the row it had under its name alone,
+// unless this run still lists a partner of that name without an ID.
+pub fn write_partners(partners: &[Partner], path: &Path) -> Result<()> {
+    let unidentified: HashSet<&str> = partners
+        .iter()
+        .filter(|partner| partner.id.is_none())
+        .map(|partner| partner.name.as_str())
+        .collect();
+    with_transaction(path, |tx| {
+        for partner in partners {
+            let id = partner.id.as_deref();
+            if let Some(id) = id.filter(|_| !unidentified.contains(partner.name.as_str())) {
+                tx.execute(
+                    "UPDATE partners SET id = ?1
+                     WHERE id IS NULL AND name = ?2
+                         AND NOT EXISTS (SELECT 1 FROM partners WHERE id = ?1)",
+                    params![id, partner.name],
+                )?;
+            }
+            let conflict = if id.is_some() {
+                "(id)"
+            } else {
+                "(name) WHERE id IS NULL"
+            };
+            tx.execute(
+                &format!(
+                    "INSERT INTO partners (id, name, category, country, country_code, region,
+                         description, website, logo_url)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                     ON CONFLICT {conflict} DO UPDATE SET
+                         name = excluded.name,
+                         category = excluded.category,
+                         country = excluded.country,
+                         country_code = excluded.country_code,
+                         region = excluded.region,
+                         description = excluded.description,
+                         website = excluded.website,
+                         logo_url = excluded.logo_url"
+                ),
+                params![
+                    id,
+                    partner.name,
+                    partner.category,
+                    partner.country,
+                    partner.country_code,
+                    partner.region,
+                    partner.description,
+                    partner.website,
+                    partner.logo_url,
+                ],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+// Upsert partner detail pages with their sectors and team members
+pub fn write_partner_details(details: &[PartnerDetails], path: &Path) -> Result<()> {
+    with_transaction(path, |tx| {
+        for detail in details {
+            tx.execute(
+                "INSERT INTO partner_details (partner_id, booth, hall, funding_stage,
+                     employee_count, founding_year, linkedin_url, twitter_url)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT (partner_id) DO UPDATE SET
+                     booth = excluded.booth,
+                     hall = excluded.hall,
+                     funding_stage = excluded.funding_stage,
+                     employee_count = excluded.employee_count,
+                     founding_year = excluded.founding_year,
+                     linkedin_url = excluded.linkedin_url,
+                     twitter_url = excluded.twitter_url",
+                params![
+                    detail.id,
+                    detail.booth,
+                    detail.hall,
+                    detail.funding_stage,
+                    detail.employee_count,
+                    detail.founding_year,
+                    detail.linkedin_url,
+                    detail.twitter_url,
+                ],
+            )?;
+
+            tx.execute(
+                "DELETE FROM partner_sectors WHERE partner_id = ?1",
+                [&detail.id],
+            )?;
+            for sector in &detail.sectors {
+                tx.execute(
+                    "INSERT OR IGNORE INTO partner_sectors (partner_id, sector) VALUES (?1, ?2)",
+                    params![detail.id, sector],
+                )?;
+            }
+
+            tx.execute(
+                "DELETE FROM team_members WHERE partner_id = ?1",
+                [&detail.id],
+            )?;
+            for (position, member) in (0_i64..).zip(&detail.team_members) {
+                tx.execute(
+                    "INSERT INTO team_members (partner_id, position, name, job_title)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![detail.id, position, member.name, member.job_title],
+                )?;
+            }
+        }
+        Ok(())
+    })
+}
+
+// Upsert sessions with their themes and speaker links
+pub fn write_sessions(sessions: &[Session], path: &Path) -> Result<()> {
+    with_transaction(path, |tx| {
+        for session in sessions {
+            tx.execute(
+                "INSERT INTO sessions (id, title, description, stage, room, start_time,
+                     end_time, timezone, format)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                 ON CONFLICT (id) DO UPDATE SET
+                     title = excluded.title,
+                     description = excluded.description,
+                     stage = excluded.stage,
+                     room = excluded.room,
+                     start_time = excluded.start_time,
+                     end_time = excluded.end_time,
+                     timezone = excluded.timezone,
+                     format = excluded.format",
+                params![
+                    session.id,
+                    session.title,
+                    session.description,
+                    session.stage,
+                    session.room,
+                    session.start_time.map(|time| time.to_rfc3339()),
+                    session.end_time.map(|time| time.to_rfc3339()),
+                    session.timezone,
+                    session.format,
+                ],
+            )?;
+
+            link_labels(
+                tx,
+                "session_themes",
+                "session_id",
+                "themes",
+                &session.id,
+                &session.themes,
+            )?;
+
+            tx.execute(
+                "DELETE FROM session_speakers WHERE session_id = ?1",
+                [&session.id],
+            )?;
+            for speaker_id in &session.speaker_ids {
+                tx.execute(
+                    "INSERT OR IGNORE INTO session_speakers (session_id, speaker_id) VALUES (?1, ?2)",
+                    params![session.id, speaker_id],
+                )?;
+            }
+        }
+        Ok(())
+    })
+}
+
+// Open an existing database without modifying it
+fn open_read_only(path: &Path) -> Result<Connection> {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open SQLite database at: {}", path.display()))
+}
+
+// Tags or themes of a speaker or session, in the order they were written
+fn read_labels(
+    conn: &Connection,
+    link_table: &str,
+    owner_column: &str,
+    label_table: &str,
+    owner_id: &str,
+) -> rusqlite::Result<Vec<String>> {
+    let label_column = format!("{}_id", label_table.trim_end_matches('s'));
+    let mut statement = conn.prepare_cached(&format!(
+        "SELECT {label_table}.name FROM {link_table}
+         JOIN {label_table} ON {label_table}.id = {link_table}.{label_column}
+         WHERE {link_table}.{owner_column} = ?1
+         ORDER BY {link_table}.rowid"
+    ))?;
+    let labels = statement.query_map([owner_id], |row| row.get(0))?;
+    labels.collect()
+}
+
+// Session start or end time stored as RFC 3339 text
+fn read_time(row: &rusqlite::Row, index: usize) -> rusqlite::Result<Option<DateTime<FixedOffset>>> {
+    let time: Option<String> = row.get(index)?;
+    time.map(|time| DateTime::parse_from_rfc3339(&time))
+        .transpose()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+// Read back the speakers written by `write_speakers`
+pub fn read_speakers(path: &Path) -> Result<Vec<Speaker>> {
+    let conn = open_read_only(path)?;
+    let read = || -> rusqlite::Result<Vec<Speaker>> {
+        let mut statement = conn.prepare(
+            "SELECT speakers.id, firstname, lastname, email, job_title, company, has_bio,
+                 has_sessions, is_official, is_partner, is_top_speaker, communication_manager,
+                 small_url, thumbnail_url, large_url, main_url
+             FROM speakers LEFT JOIN images ON images.speaker_id = speakers.id
+             ORDER BY speakers.rowid",
+        )?;
+        let rows = statement.query_map([], |row| {
+            let main_url: Option<String> = row.get(15)?;
+            Ok(Speaker {
+                id: row.get(0)?,
+                firstname: row.get(1)?,
+                lastname: row.get(2)?,
+                email: row.get(3)?,
+                job_title: row.get(4)?,
+                company: row.get(5)?,
+                tags: Vec::new(),
+                themes: Vec::new(),
+                image: main_url
+                    .map(|u| -> rusqlite::Result<Image> {
+                        Ok(Image {
+                            s: row.get(12)?,
+                            t: row.get(13)?,
+                            l: row.get(14)?,
+                            u,
+                        })
+                    })
+                    .transpose()?,
+                has_bio: row.get(6)?,
+                has_sessions: row.get(7)?,
+                is_official: row.get(8)?,
+                is_partner: row.get(9)?,
+                top: row.get(10)?,
+                communication_manager: row.get(11)?,
+            })
+        })?;
+
+        let mut speakers = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+        for speaker in &mut speakers {
+            speaker.tags = read_labels(&conn, "speaker_tags", "speaker_id", "tags", &speaker.id)?;
+            speaker.themes =
+                read_labels(&conn, "speaker_themes", "speaker_id", "themes", &speaker.id)?;
+        }
+        Ok(speakers)
+    };
+    read().context("Failed to read speakers from SQLite")
+}
+
+// Read back the partners written by `write_partners`
+pub fn read_partners(path: &Path) -> Result<Vec<Partner>> {
+    let conn = open_read_only(path)?;
+    let read = || -> rusqlite::Result<Vec<Partner>> {
+        let mut statement = conn.prepare(
+            "SELECT id, name, category, country, country_code, region, description, website,
+                 logo_url
+             FROM partners ORDER BY partner_key",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok(Partner {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                category: row.get(2)?,
+                country: row.get(3)?,
+                country_code: row.get(4)?,
+                region: row.get(5)?,
+                description: row.get(6)?,
+                website: row.get(7)?,
+                logo_url: row.get(8)?,
+            })
+        })?;
+        rows.collect()
+    };
+    read().context("Failed to read partners from SQLite")
+}
+
+// Read back the sessions written by `write_sessions`
+pub fn read_sessions(path: &Path) -> Result<Vec<Session>> {
+    let conn = open_read_only(path)?;
+    let read = || -> rusqlite::Result<Vec<Session>> {
+        let mut statement = conn.prepare(
+            "SELECT id, title, description, stage, room, start_time, end_time, timezone, format
+             FROM sessions ORDER BY rowid",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok(Session {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                description: row.get(2)?,
+                stage: row.get(3)?,
+                room: row.get(4)?,
+                start_time: read_time(row, 5)?,
+                end_time: read_time(row, 6)?,
+                timezone: row.get(7)?,
+                format: row.get(8)?,
+                themes: Vec::new(),
+                speaker_ids: Vec::new(),
+            })
+        })?;
+
+        let mut sessions = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut speaker_ids = conn.prepare(
+            "SELECT speaker_id FROM session_speakers WHERE session_id = ?1 ORDER BY rowid",
+        )?;
+        for session in &mut sessions {
+            session.themes =
+                read_labels(&conn, "session_themes", "session_id", "themes", &session.id)?;
+            session.speaker_ids = speaker_ids
+                .query_map([&session.id], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+        }
+        Ok(sessions)
+    };
+    read().context("Failed to read sessions from SQLite")
+}
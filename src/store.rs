@@ -0,0 +1,280 @@
+// Append-only snapshot history for VivaTech
+// Every recorded scrape gets its own records file and a line in `index.jsonl`
+// holding when and where it was taken and a hash of its content, unless it
+// holds the same records as the latest snapshot of its entity. Nothing is
+// ever rewritten, so past snapshots can be listed, compared and exported.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::diff::{self, ChangedRecord, Entity, FieldChange, Snapshot};
+use crate::partners::Partner;
+use crate::sessions::Session;
+use crate::speakers::Speaker;
+
+const INDEX_FILE: &str = "index.jsonl";
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+// Alias for the most recent snapshot
+pub const LATEST: &str = "latest";
+
+// Metadata of a recorded snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub entity: Entity,
+    pub taken_at: DateTime<Utc>,
+    // URL or path the pages were read from
+    pub source: String,
+    // SHA-256 of the records file
+    pub content_hash: String,
+    pub records: usize,
+}
+
+// What happened to a record in one snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", content = "fields", rename_all = "lowercase")]
+pub enum RecordChange {
+    Added,
+    Removed,
+    Changed(Vec<FieldChange>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HistoryEntry {
+    pub snapshot: SnapshotInfo,
+    pub label: String,
+    pub change: RecordChange,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    root: PathBuf,
+}
+
+impl SnapshotStore {
+    // Open the store in `root`, creating it if needed
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(root.join(SNAPSHOTS_DIR))
+            .with_context(|| format!("Failed to create snapshot store at: {}", root.display()))?;
+        Ok(Self { root })
+    }
+
+    fn records_path(&self, id: &str) -> PathBuf {
+        self.root.join(SNAPSHOTS_DIR).join(format!("{id}.jsonl"))
+    }
+
+    // Record a new snapshot of `records`, or return the latest snapshot of
+    // `entity` if its content is identical
+    pub fn record<T: Serialize>(
+        &self,
+        entity: Entity,
+        source: &str,
+        records: &[T],
+    ) -> Result<SnapshotInfo> {
+        let mut content = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut content, record).context("Failed to serialize record")?;
+            content.push(b'\n');
+        }
+        let content_hash = format!("{:x}", Sha256::digest(&content));
+        if let Some(latest) = self
+            .latest(entity)?
+            .filter(|latest| latest.content_hash == content_hash)
+        {
+            return Ok(latest);
+        }
+
+        let taken_at = Utc::now();
+        let stem = format!(
+            "{}-{}-{}",
+            entity.name(),
+            taken_at.format("%Y%m%dT%H%M%S%3fZ"),
+            &content_hash[..8]
+        );
+        // Records can change and change back within a millisecond, so an ID
+        // already taken gets a counter
+        let mut id = stem.clone();
+        for n in 2.. {
+            let path = self.records_path(&id);
+            match File::create_new(&path).and_then(|mut file| file.write_all(&content)) {
+                Ok(()) => break,
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    id = format!("{stem}-{n}");
+                }
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("Failed to write snapshot: {}", path.display()))
+                }
+            }
+        }
+
+        let info = SnapshotInfo {
+            id,
+            entity,
+            taken_at,
+            source: source.to_string(),
+            content_hash,
+            records: records.len(),
+        };
+        // The index line is added last, so it only lists complete snapshots
+        let mut index = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.root.join(INDEX_FILE))
+            .context("Failed to open snapshot index")?;
+        let mut line = serde_json::to_string(&info).context("Failed to serialize snapshot")?;
+        line.push('\n');
+        index
+            .write_all(line.as_bytes())
+            .context("Failed to update snapshot index")?;
+        Ok(info)
+    }
+
+    // Every snapshot, oldest first
+    pub fn list(&self) -> Result<Vec<SnapshotInfo>> {
+        let index = match fs::read_to_string(self.root.join(INDEX_FILE)) {
+            Ok(index) => index,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err).context("Failed to read snapshot index"),
+        };
+        let mut snapshots: Vec<SnapshotInfo> = index
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .context("Corrupt snapshot index")?;
+        snapshots.sort_by_key(|snapshot| snapshot.taken_at);
+        Ok(snapshots)
+    }
+
+    // Most recent snapshot of `entity`, if any
+    pub fn latest(&self, entity: Entity) -> Result<Option<SnapshotInfo>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .rfind(|snapshot| snapshot.entity == entity))
+    }
+
+    // Snapshot by ID, unique ID prefix, or `latest`
+    pub fn find(&self, id: &str) -> Result<SnapshotInfo> {
+        let snapshots = self.list()?;
+        if id == LATEST {
+            return snapshots
+                .last()
+                .cloned()
+                .context("The snapshot store is empty");
+        }
+        if let Some(snapshot) = snapshots.iter().find(|snapshot| snapshot.id == id) {
+            return Ok(snapshot.clone());
+        }
+        let mut matches = snapshots
+            .iter()
+            .filter(|snapshot| snapshot.id.starts_with(id));
+        match (matches.next(), matches.next()) {
+            (Some(snapshot), None) => Ok(snapshot.clone()),
+            (Some(_), Some(_)) => anyhow::bail!("Snapshot ID {id:?} is ambiguous"),
+            (None, _) => anyhow::bail!("No snapshot {id:?} in the store"),
+        }
+    }
+
+    fn load_records<T: DeserializeOwned>(&self, info: &SnapshotInfo) -> Result<Vec<T>> {
+        let path = self.records_path(&info.id);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read snapshot: {}", path.display()))?;
+        content
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Corrupt snapshot: {}", path.display()))
+    }
+
+    pub fn load_speakers(&self, info: &SnapshotInfo) -> Result<Vec<Speaker>> {
+        anyhow::ensure!(
+            info.entity == Entity::Speakers,
+            "Snapshot {} holds {}, not speakers",
+            info.id,
+            info.entity.name()
+        );
+        self.load_records(info)
+    }
+
+    pub fn load_partners(&self, info: &SnapshotInfo) -> Result<Vec<Partner>> {
+        anyhow::ensure!(
+            info.entity == Entity::Partners,
+            "Snapshot {} holds {}, not partners",
+            info.id,
+            info.entity.name()
+        );
+        self.load_records(info)
+    }
+
+    pub fn load_sessions(&self, info: &SnapshotInfo) -> Result<Vec<Session>> {
+        anyhow::ensure!(
+            info.entity == Entity::Sessions,
+            "Snapshot {} holds {}, not sessions",
+            info.id,
+            info.entity.name()
+        );
+        self.load_records(info)
+    }
+
+    // Snapshot in the form used for comparisons
+    pub fn load(&self, info: &SnapshotInfo) -> Result<Snapshot> {
+        match info.entity {
+            Entity::Speakers => Snapshot::from_speakers(self.load_speakers(info)?),
+            Entity::Partners => Snapshot::from_partners(self.load_partners(info)?),
+            Entity::Sessions => Snapshot::from_sessions(self.load_sessions(info)?),
+        }
+    }
+
+    // When the record with `record_id` was added, changed or removed
+    pub fn history(&self, record_id: &str) -> Result<Vec<HistoryEntry>> {
+        let snapshots = self.list()?;
+        let mut entries = Vec::new();
+
+        for entity in [Entity::Speakers, Entity::Partners, Entity::Sessions] {
+            let mut previous = Snapshot::empty(entity);
+            for info in snapshots.iter().filter(|info| info.entity == entity) {
+                let current = self.load(info)?;
+                for entity_diff in diff::compare(&previous, &current) {
+                    let added = entity_diff
+                        .added
+                        .into_iter()
+                        .map(|r| (r.id, r.label, RecordChange::Added));
+                    let removed = entity_diff
+                        .removed
+                        .into_iter()
+                        .map(|r| (r.id, r.label, RecordChange::Removed));
+                    let changed = entity_diff.changed.into_iter().map(
+                        |ChangedRecord { id, label, changes }| {
+                            (id, label, RecordChange::Changed(changes))
+                        },
+                    );
+                    entries.extend(
+                        added
+                            .chain(removed)
+                            .chain(changed)
+                            .filter(|(id, _, _)| id == record_id)
+                            .map(|(_, label, change)| HistoryEntry {
+                                snapshot: info.clone(),
+                                label,
+                                change,
+                            }),
+                    );
+                }
+                previous = current;
+            }
+        }
+
+        entries.sort_by_key(|entry| entry.snapshot.taken_at);
+        Ok(entries)
+    }
+}
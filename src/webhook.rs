@@ -0,0 +1,258 @@
+// Change notifications for VivaTech
+// When a recorded snapshot adds or changes speakers, partners or sessions,
+// the changes are POSTed as JSON to webhooks: Slack-compatible ones get a
+// `text` message, generic ones the message plus every added, changed and
+// removed record.
+// With a secret, each body is signed with HMAC-SHA256. Deliveries identify
+// the scraper honestly and are retried like page fetches, except after a
+// timeout. Webhook URLs embed their credentials, so only their host is logged.
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Url;
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::Duration;
+
+use crate::diff::EntityDiff;
+use crate::fetch::{self, AttemptError, RetryPolicy};
+use crate::store::SnapshotInfo;
+
+// Sent instead of the browser User-Agent used for page fetches
+pub const USER_AGENT: &str = concat!("vivatech-scraper/", env!("CARGO_PKG_VERSION"));
+
+// Header holding `sha256=<hex HMAC of the body>` when a secret is set
+pub const SIGNATURE_HEADER: &str = "X-Vivatech-Signature-256";
+
+// Placeholders: {entity}, {added}, {changed}, {removed}, {records},
+// {snapshot}, {source} and {changes} (one line per record)
+pub const DEFAULT_TEMPLATE: &str =
+    "VivaTech {entity}: {added} new, {changed} updated, {removed} removed\n{changes}";
+
+// Records listed by {changes}; the rest are summed up in one line
+const MAX_LISTED_RECORDS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookKind {
+    // Slack incoming webhook, or anything accepting `{"text": ...}`
+    Slack,
+    // The full change set as JSON
+    Generic,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pub url: String,
+    pub kind: WebhookKind,
+}
+
+// Changes between two snapshots of one entity
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeEvent<'a> {
+    pub previous: &'a SnapshotInfo,
+    pub snapshot: &'a SnapshotInfo,
+    pub diff: &'a EntityDiff,
+}
+
+impl ChangeEvent<'_> {
+    // Only new and updated records are worth a notification
+    pub fn is_notable(&self) -> bool {
+        !self.diff.added.is_empty() || !self.diff.changed.is_empty()
+    }
+}
+
+#[derive(Serialize)]
+struct GenericPayload<'a> {
+    event: &'static str,
+    message: &'a str,
+    snapshot: &'a SnapshotInfo,
+    previous_snapshot: &'a SnapshotInfo,
+    #[serde(flatten)]
+    diff: &'a EntityDiff,
+}
+
+// Slack reads `&`, `<` and `>` as markup, e.g. `<!channel>` pings everyone
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// Scheme and host of a webhook URL, whose path usually holds its secret token
+fn redacted_url(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            url.host_str()
+                .map(|host| format!("{}://{host}", url.scheme()))
+        })
+        .unwrap_or_else(|| "<invalid URL>".to_string())
+}
+
+// One line per added, changed and removed record
+fn change_lines(diff: &EntityDiff) -> String {
+    let mut lines: Vec<String> = diff
+        .added
+        .iter()
+        .map(|record| format!("• New: {}", record.label))
+        .chain(diff.changed.iter().map(|record| {
+            let fields: Vec<&str> = record.changes.iter().map(|c| c.field.as_str()).collect();
+            format!("• Updated: {} ({})", record.label, fields.join(", "))
+        }))
+        .chain(
+            diff.removed
+                .iter()
+                .map(|record| format!("• Removed: {}", record.label)),
+        )
+        .collect();
+
+    if lines.len() > MAX_LISTED_RECORDS {
+        let more = lines.len() - MAX_LISTED_RECORDS;
+        lines.truncate(MAX_LISTED_RECORDS);
+        lines.push(format!("… and {more} more"));
+    }
+    lines.join("\n")
+}
+
+// Fill the template's placeholders; unknown ones are left as they are
+// Values sent to Slack are escaped, so scraped text cannot turn into markup.
+pub fn render_message(template: &str, event: &ChangeEvent, kind: WebhookKind) -> String {
+    let diff = event.diff;
+    [
+        ("{entity}", diff.entity.name().to_string()),
+        ("{added}", diff.added.len().to_string()),
+        ("{changed}", diff.changed.len().to_string()),
+        ("{removed}", diff.removed.len().to_string()),
+        ("{records}", event.snapshot.records.to_string()),
+        ("{snapshot}", event.snapshot.id.clone()),
+        ("{source}", event.snapshot.source.clone()),
+        ("{changes}", change_lines(diff)),
+    ]
+    .into_iter()
+    .fold(template.to_string(), |message, (placeholder, value)| {
+        let value = match kind {
+            WebhookKind::Slack => escape_slack(&value),
+            WebhookKind::Generic => value,
+        };
+        message.replace(placeholder, &value)
+    })
+}
+
+// JSON body sent to a webhook of `kind`
+pub fn payload(kind: WebhookKind, event: &ChangeEvent, message: &str) -> Result<Vec<u8>> {
+    let body = match kind {
+        WebhookKind::Slack => serde_json::to_vec(&serde_json::json!({ "text": message })),
+        WebhookKind::Generic => serde_json::to_vec(&GenericPayload {
+            event: "vivatech.changes",
+            message,
+            snapshot: event.snapshot,
+            previous_snapshot: event.previous,
+            diff: event.diff,
+        }),
+    };
+    body.context("Failed to serialize webhook payload")
+}
+
+// Signature header value for `body`
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+pub struct Notifier {
+    client: Client,
+    webhooks: Vec<Webhook>,
+    template: String,
+    secret: Option<String>,
+    retry_policy: RetryPolicy,
+}
+
+impl Notifier {
+    pub fn new(webhooks: Vec<Webhook>, timeout: Duration) -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(timeout)
+            .build()
+            .context("Failed to build webhook HTTP client")?;
+        Ok(Self {
+            client,
+            webhooks,
+            template: DEFAULT_TEMPLATE.to_string(),
+            secret: None,
+            retry_policy: RetryPolicy::default(),
+        })
+    }
+
+    #[must_use]
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_secret(mut self, secret: Option<String>) -> Self {
+        self.secret = secret;
+        self
+    }
+
+    // Retry rate-limited (429) and failed (5xx) deliveries, and connections
+    // that failed before anything was sent
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    fn post(&self, webhook: &Webhook, body: Vec<u8>) -> Result<()> {
+        let signature = self.secret.as_ref().map(|secret| sign(secret, &body));
+        let target = redacted_url(&webhook.url);
+        fetch::with_retries(&target, &self.retry_policy, || {
+            let mut request = self
+                .client
+                .post(&webhook.url)
+                .header(CONTENT_TYPE, "application/json");
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+            let response = request
+                .body(body.clone())
+                .send()
+                .map_err(|e| AttemptError::post_failed(e.without_url()))?;
+            if !response.status().is_success() {
+                return Err(AttemptError::status(&response));
+            }
+            Ok(())
+        })
+        .with_context(|| format!("Webhook {target} failed"))
+    }
+
+    // Send `event` to every webhook if it is notable, returning whether it was sent.
+    // All webhooks are tried even when some fail.
+    pub fn notify(&self, event: &ChangeEvent) -> Result<bool> {
+        if !event.is_notable() {
+            return Ok(false);
+        }
+        let mut failures = Vec::new();
+        for webhook in &self.webhooks {
+            let message = render_message(&self.template, event, webhook.kind);
+            let result =
+                payload(webhook.kind, event, &message).and_then(|body| self.post(webhook, body));
+            if let Err(e) = result {
+                failures.push(format!("{e:#}"));
+            }
+        }
+        if !failures.is_empty() {
+            anyhow::bail!(
+                "{} of {} webhooks failed: {}",
+                failures.len(),
+                self.webhooks.len(),
+                failures.join("; ")
+            );
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,211 @@
+// Excel workbook export for VivaTech
+// Writes a Speakers and a Partners sheet with the CSV columns, plus a Summary
+// sheet counting speakers by company and tag and partners by country. Sheets
+// with no data are left out, so a speakers run yields Speakers and Summary.
+
+use anyhow::{Context, Result};
+use rust_xlsxwriter::{ColNum, Format, RowNum, Url, Workbook, Worksheet};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::partners::Partner;
+use crate::speakers::Speaker;
+
+// Default file of the combined speakers and partners workbook
+pub const DEFAULT_WORKBOOK_OUTPUT: &str = "vivatech_2025.xlsx";
+
+// Widest a column may grow when fitted to its content
+const MAX_COLUMN_WIDTH: u32 = 400;
+
+const SPEAKER_HEADERS: [&str; 18] = [
+    "ID",
+    "FirstName",
+    "LastName",
+    "Email",
+    "JobTitle",
+    "Company",
+    "Tags",
+    "Themes",
+    "HasBio",
+    "HasSessions",
+    "IsOfficial",
+    "IsPartner",
+    "IsTopSpeaker",
+    "CommunicationManager",
+    "ImageSmallURL",
+    "ImageThumbnailURL",
+    "ImageLargeURL",
+    "ImageMainURL",
+];
+
+const PARTNER_HEADERS: [&str; 9] = [
+    "ID",
+    "CompanyName",
+    "Category",
+    "Country",
+    "CountryCode",
+    "Region",
+    "Description",
+    "Website",
+    "LogoURL",
+];
+
+fn row_number(index: usize) -> Result<RowNum> {
+    RowNum::try_from(index + 1).context("Too many rows for an Excel worksheet")
+}
+
+// Bold header row, frozen while scrolling, with an autofilter over the data
+fn write_table_header(sheet: &mut Worksheet, headers: &[&str], rows: usize) -> Result<()> {
+    let bold = Format::new().set_bold();
+    sheet.write_row_with_format(0, 0, headers.iter().copied(), &bold)?;
+    sheet.set_freeze_panes(1, 0)?;
+    let last_col = ColNum::try_from(headers.len() - 1).unwrap_or(ColNum::MAX);
+    sheet.autofilter(0, 0, row_number(rows)? - 1, last_col)?;
+    Ok(())
+}
+
+// Write `url` as a clickable hyperlink, or as plain text if Excel rejects it
+fn write_link(sheet: &mut Worksheet, row: RowNum, col: ColNum, url: Option<&str>) -> Result<()> {
+    let Some(url) = url.filter(|url| !url.is_empty()) else {
+        return Ok(());
+    };
+    if sheet.write_url(row, col, Url::new(url)).is_err() {
+        sheet.write_string(row, col, url)?;
+    }
+    Ok(())
+}
+
+fn write_speakers_sheet(sheet: &mut Worksheet, speakers: &[Speaker]) -> Result<()> {
+    sheet.set_name("Speakers")?;
+    write_table_header(sheet, &SPEAKER_HEADERS, speakers.len())?;
+
+    for (index, speaker) in speakers.iter().enumerate() {
+        let row = row_number(index)?;
+        let text = [
+            Some(speaker.id.as_str()),
+            Some(&speaker.firstname),
+            Some(&speaker.lastname),
+            speaker.email.as_deref(),
+            Some(&speaker.job_title),
+            Some(&speaker.company),
+            Some(&speaker.tags.join(", ")),
+            Some(&speaker.themes.join(", ")),
+        ];
+        sheet.write_row(row, 0, text)?;
+        let flags = [
+            speaker.has_bio,
+            speaker.has_sessions,
+            speaker.is_official,
+            speaker.is_partner,
+            speaker.top,
+        ];
+        sheet.write_row(row, 8, flags)?;
+        sheet.write(row, 13, speaker.communication_manager.as_deref())?;
+        if let Some(image) = &speaker.image {
+            let urls = [&image.s, &image.t, &image.l].map(Option::as_deref);
+            for (col, url) in (14..).zip(urls.into_iter().chain([Some(image.u.as_str())])) {
+                write_link(sheet, row, col, url)?;
+            }
+        }
+    }
+
+    sheet.set_autofit_max_width(MAX_COLUMN_WIDTH).autofit();
+    Ok(())
+}
+
+fn write_partners_sheet(sheet: &mut Worksheet, partners: &[Partner]) -> Result<()> {
+    sheet.set_name("Partners")?;
+    write_table_header(sheet, &PARTNER_HEADERS, partners.len())?;
+
+    for (index, partner) in partners.iter().enumerate() {
+        let row = row_number(index)?;
+        let text = [
+            partner.id.as_deref(),
+            Some(&partner.name),
+            Some(&partner.category),
+            partner.country.as_deref(),
+            partner.country_code.as_deref(),
+            partner.region.as_deref(),
+            partner.description.as_deref(),
+        ];
+        sheet.write_row(row, 0, text)?;
+        write_link(sheet, row, 7, partner.website.as_deref())?;
+        write_link(sheet, row, 8, partner.logo_url.as_deref())?;
+    }
+
+    sheet.set_autofit_max_width(MAX_COLUMN_WIDTH).autofit();
+    Ok(())
+}
+
+// Count non-empty values, most frequent first and then by name
+fn count_by<'a>(values: impl Iterator<Item = &'a str>) -> Vec<(&'a str, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in values.filter(|value| !value.is_empty()) {
+        *counts.entry(value).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+}
+
+fn write_summary_sheet(
+    sheet: &mut Worksheet,
+    speakers: &[Speaker],
+    partners: &[Partner],
+) -> Result<()> {
+    sheet.set_name("Summary")?;
+    let bold = Format::new().set_bold();
+
+    let mut sections = Vec::new();
+    if !speakers.is_empty() {
+        let companies = count_by(speakers.iter().map(|s| s.company.as_str()));
+        let tags = count_by(
+            speakers
+                .iter()
+                .flat_map(|s| s.tags.iter().map(String::as_str)),
+        );
+        sections.push(("Company", "Speakers", companies));
+        sections.push(("Tag", "Speakers", tags));
+    }
+    if !partners.is_empty() {
+        let countries = count_by(partners.iter().filter_map(|p| p.country.as_deref()));
+        sections.push(("Country", "Partners", countries));
+    }
+
+    // Sections sit side by side, separated by an empty column
+    for (col, (label, unit, counts)) in (0..).step_by(3).zip(sections) {
+        sheet.write_row_with_format(0, col, [label, unit], &bold)?;
+        for (index, (value, count)) in counts.into_iter().enumerate() {
+            let row = row_number(index)?;
+            sheet.write(row, col, value)?;
+            sheet.write(row, col + 1, u32::try_from(count).unwrap_or(u32::MAX))?;
+        }
+    }
+    sheet.set_freeze_panes(1, 0)?;
+
+    sheet.set_autofit_max_width(MAX_COLUMN_WIDTH).autofit();
+    Ok(())
+}
+
+// Write a workbook with a sheet per non-empty entity and a Summary sheet
+pub fn write_workbook(
+    speakers: &[Speaker],
+    partners: &[Partner],
+    output_path: &Path,
+) -> Result<()> {
+    let mut workbook = Workbook::new();
+    if !speakers.is_empty() {
+        write_speakers_sheet(workbook.add_worksheet(), speakers)?;
+    }
+    if !partners.is_empty() {
+        write_partners_sheet(workbook.add_worksheet(), partners)?;
+    }
+    write_summary_sheet(workbook.add_worksheet(), speakers, partners)?;
+
+    workbook.save(output_path).with_context(|| {
+        format!(
+            "Failed to write Excel workbook at: {}",
+            output_path.display()
+        )
+    })
+}
@@ -0,0 +1,65 @@
+// Tests for the offline city gazetteer
+
+use pretty_assertions::assert_eq;
+use vivatech::cities::{self, CityResolution};
+
+// Country and region of a resolved city
+fn place(text: &str) -> Option<(&'static str, &'static str)> {
+    match cities::resolve(text) {
+        CityResolution::Found(city) | CityResolution::Dominant { city, .. } => {
+            Some((city.country, city.region))
+        }
+        _ => None,
+    }
+}
+
+#[test]
+fn resolves_cities_by_name_and_alternate_name() {
+    assert_eq!(place("Paris"), Some(("FR", "Île-de-France")));
+    assert_eq!(place("München"), Some(("DE", "Bavaria")));
+    assert_eq!(place("Bangalore"), Some(("IN", "Karnataka")));
+    assert_eq!(place("sao paulo"), Some(("BR", "São Paulo")));
+    assert_eq!(place("東京"), Some(("JP", "Tokyo")));
+}
+
+#[test]
+fn qualifiers_select_among_homonyms() {
+    assert_eq!(place("Paris, Texas"), Some(("US", "Texas")));
+    assert_eq!(place("Paris, TN"), Some(("US", "Tennessee")));
+    assert_eq!(place("London, Ontario, Canada"), Some(("CA", "Ontario")));
+    assert_eq!(place("Cambridge, UK"), Some(("GB", "England")));
+    // Postcodes and other unknown qualifiers are ignored
+    assert_eq!(place("Paris, 75008"), Some(("FR", "Île-de-France")));
+    // A known place that contradicts every candidate is not guessed around
+    assert_eq!(cities::resolve("Paris, Ontario"), CityResolution::Unknown);
+}
+
+#[test]
+fn reports_the_homonyms_of_a_far_larger_city() {
+    let CityResolution::Dominant { city, homonyms } = cities::resolve("London") else {
+        panic!("London should resolve to its largest homonym");
+    };
+    assert_eq!(city.country, "GB");
+    let countries: Vec<&str> = homonyms.iter().map(|city| city.country).collect();
+    assert!(countries.contains(&"CA"), "{countries:?}");
+
+    assert!(matches!(
+        cities::resolve("München"),
+        CityResolution::Found(_)
+    ));
+}
+
+#[test]
+fn reports_ambiguous_cities() {
+    let CityResolution::Ambiguous(candidates) = cities::resolve("Cambridge") else {
+        panic!("Cambridge should be ambiguous");
+    };
+    let countries: Vec<&str> = candidates.iter().map(|city| city.country).collect();
+    assert_eq!(countries, vec!["GB", "US"]);
+
+    assert!(matches!(
+        cities::resolve("Valencia"),
+        CityResolution::Ambiguous(_)
+    ));
+    assert_eq!(cities::resolve("Atlantis"), CityResolution::Unknown);
+}
@@ -0,0 +1,1238 @@
+// End-to-end tests running the CLI binary against a local mock HTTP server
+
+mod common;
+
+use common::server::{MockResponse, MockServer};
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::{Duration, Instant};
+
+const DEBUG_HTML: &str = "debug_vivatech_page.html";
+
+fn run_cli(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_vivatech"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run vivatech binary")
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn scrapes_speakers_from_server() {
+    let server = MockServer::start();
+    server.route(
+        "/speakers",
+        vec![MockResponse::ok(common::fixture("speakers_flight.html"))],
+    );
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/speakers"),
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("out.csv")).unwrap(),
+        common::fixture("speakers_flight.expected.csv")
+    );
+    assert!(!dir.path().join(DEBUG_HTML).exists());
+
+    let requests = server.requests_to("/speakers");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert!(requests[0]
+        .header("User-Agent")
+        .is_some_and(|agent| agent.starts_with("Mozilla/5.0")));
+}
+
+#[test]
+fn scrapes_partners_from_server() {
+    let server = MockServer::start();
+    server.route(
+        "/partners",
+        vec![MockResponse::ok(common::fixture("partners_next_data.html"))],
+    );
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "partners",
+            "--url",
+            &server.url("/partners"),
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("out.csv")).unwrap(),
+        common::fixture("partners_next_data.expected.csv")
+    );
+}
+
+#[test]
+fn follows_redirects() {
+    let server = MockServer::start();
+    server
+        .route("/old-speakers", vec![MockResponse::redirect("/speakers")])
+        .route(
+            "/speakers",
+            vec![MockResponse::ok(common::fixture("speakers_flight.html"))],
+        );
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/old-speakers"),
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir.path().join("out.csv").exists());
+    assert_eq!(server.requests_to("/speakers").len(), 1);
+}
+
+#[test]
+fn fails_on_not_found() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/missing"),
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("404"), "{}", stderr(&output));
+    assert!(!dir.path().join("out.csv").exists());
+    assert!(!dir.path().join(DEBUG_HTML).exists());
+}
+
+#[test]
+fn fails_on_server_error() {
+    let server = MockServer::start();
+    server.route("/speakers", vec![MockResponse::status(500)]);
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/speakers"),
+            "--retries",
+            "2",
+            "--retry-delay",
+            "10",
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("500"), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Giving up after 3 attempts"));
+    assert_eq!(server.requests_to("/speakers").len(), 3);
+    assert!(!dir.path().join("out.csv").exists());
+}
+
+#[test]
+fn retries_transient_errors() {
+    let server = MockServer::start();
+    server.route(
+        "/speakers",
+        vec![
+            MockResponse::status(503),
+            MockResponse::status(429),
+            MockResponse::ok(common::fixture("speakers_flight.html")),
+        ],
+    );
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/speakers"),
+            "--retry-delay",
+            "10",
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(server.requests_to("/speakers").len(), 3);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("out.csv")).unwrap(),
+        common::fixture("speakers_flight.expected.csv")
+    );
+}
+
+#[test]
+fn honours_retry_after() {
+    let server = MockServer::start();
+    server.route(
+        "/partners",
+        vec![
+            MockResponse::status(429).header("Retry-After", "1"),
+            MockResponse::ok(common::fixture("partners_next_data.html")),
+        ],
+    );
+    let dir = tempfile::tempdir().unwrap();
+
+    let started = Instant::now();
+    let output = run_cli(
+        dir.path(),
+        &[
+            "partners",
+            "--url",
+            &server.url("/partners"),
+            "--retry-delay",
+            "10",
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.requests_to("/partners").len(), 2);
+}
+
+#[test]
+fn stops_when_retry_budget_is_exhausted() {
+    let server = MockServer::start();
+    server.route(
+        "/speakers",
+        vec![MockResponse::status(503).header("Retry-After", "30")],
+    );
+    let dir = tempfile::tempdir().unwrap();
+
+    let started = Instant::now();
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/speakers"),
+            "--retry-budget",
+            "5",
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("Retry budget"),
+        "{}",
+        stderr(&output)
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(server.requests_to("/speakers").len(), 1);
+}
+
+#[test]
+fn does_not_retry_client_errors() {
+    let server = MockServer::start();
+    server.route("/speakers", vec![MockResponse::status(404)]);
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/speakers"),
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert_eq!(server.requests_to("/speakers").len(), 1);
+}
+
+#[test]
+fn times_out_on_slow_response() {
+    let server = MockServer::start();
+    server.route(
+        "/speakers",
+        vec![
+            MockResponse::ok(common::fixture("speakers_flight.html")).delay(Duration::from_secs(3))
+        ],
+    );
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/speakers"),
+            "--timeout",
+            "1",
+            "--retries",
+            "0",
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(!dir.path().join("out.csv").exists());
+}
+
+#[test]
+fn tolerates_slow_response_within_timeout() {
+    let server = MockServer::start();
+    server.route(
+        "/partners",
+        vec![MockResponse::ok(common::fixture("partners_next_data.html"))
+            .delay(Duration::from_millis(500))],
+    );
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "partners",
+            "--url",
+            &server.url("/partners"),
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir.path().join("out.csv").exists());
+}
+
+#[test]
+fn dumps_debug_html_when_page_has_no_data() {
+    let server = MockServer::start();
+    let page = common::fixture("no_data.html");
+    server.route("/speakers", vec![MockResponse::ok(page.clone())]);
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/speakers"),
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Could not find speaker data"));
+    assert_eq!(
+        fs::read_to_string(dir.path().join(DEBUG_HTML)).unwrap(),
+        page
+    );
+    assert!(!dir.path().join("out.csv").exists());
+}
+
+#[test]
+fn offline_input_does_not_dump_debug_html() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = common::fixture_path("no_data.html");
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--input",
+            input.to_str().unwrap(),
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(!dir.path().join(DEBUG_HTML).exists());
+}
+
+#[test]
+fn revalidates_cached_page_with_etag() {
+    let server = MockServer::start();
+    server.route(
+        "/speakers",
+        vec![
+            MockResponse::ok(common::fixture("speakers_flight.html")).header("ETag", "\"v1\""),
+            MockResponse::status(304),
+        ],
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let url = server.url("/speakers");
+
+    for output_file in ["first.csv", "second.csv"] {
+        let output = run_cli(dir.path(), &["speakers", "--url", &url, "-o", output_file]);
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+
+    let requests = server.requests_to("/speakers");
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].header("If-None-Match"), None);
+    assert_eq!(requests[1].header("If-None-Match"), Some("\"v1\""));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("second.csv")).unwrap(),
+        common::fixture("speakers_flight.expected.csv")
+    );
+}
+
+#[test]
+fn revalidates_cached_page_with_last_modified() {
+    let server = MockServer::start();
+    let last_modified = "Wed, 11 Jun 2025 08:00:00 GMT";
+    server.route(
+        "/partners",
+        vec![
+            MockResponse::ok(common::fixture("partners_next_data.html"))
+                .header("Last-Modified", last_modified),
+            MockResponse::status(304),
+        ],
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let url = server.url("/partners");
+
+    for _ in 0..2 {
+        let output = run_cli(dir.path(), &["partners", "--url", &url, "-o", "out.csv"]);
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+
+    let requests = server.requests_to("/partners");
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].header("If-Modified-Since"), Some(last_modified));
+}
+
+#[test]
+fn reuses_fresh_cache_without_request() {
+    let server = MockServer::start();
+    server.route(
+        "/speakers",
+        vec![MockResponse::ok(common::fixture("speakers_flight.html"))],
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let url = server.url("/speakers");
+
+    for _ in 0..2 {
+        let output = run_cli(
+            dir.path(),
+            &[
+                "speakers",
+                "--url",
+                &url,
+                "--cache-dir",
+                "cache",
+                "--max-age",
+                "3600",
+                "-o",
+                "out.csv",
+            ],
+        );
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+
+    assert_eq!(server.requests_to("/speakers").len(), 1);
+    assert!(dir.path().join("cache").is_dir());
+}
+
+#[test]
+fn no_cache_always_downloads() {
+    let server = MockServer::start();
+    server.route(
+        "/speakers",
+        vec![MockResponse::ok(common::fixture("speakers_flight.html")).header("ETag", "\"v1\"")],
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let url = server.url("/speakers");
+
+    for _ in 0..2 {
+        let output = run_cli(
+            dir.path(),
+            &["speakers", "--url", &url, "--no-cache", "-o", "out.csv"],
+        );
+        assert!(output.status.success(), "{}", stderr(&output));
+    }
+
+    let requests = server.requests_to("/speakers");
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].header("If-None-Match"), None);
+    assert!(!dir.path().join(".vivatech-cache").exists());
+}
+
+#[test]
+fn crawls_speaker_detail_pages() {
+    let server = MockServer::start();
+    server
+        .route(
+            "/speakers",
+            vec![MockResponse::ok(common::fixture("speakers_flight.html"))],
+        )
+        .route(
+            "/speakers/9e3a8589-c629-f011-8b3d-6045bd903b46",
+            vec![MockResponse::ok(common::fixture(
+                "speaker_detail_jensen.html",
+            ))],
+        )
+        .route(
+            "/speakers/55208d04-5e09-f011-aaa7-6045bd9d3cdc",
+            vec![MockResponse::ok(common::fixture("speaker_detail_zoe.html"))],
+        );
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &server.url("/speakers"),
+            "--with-details",
+            "--concurrency",
+            "2",
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    // The third speaker has no detail page and is skipped
+    assert_eq!(
+        fs::read_to_string(dir.path().join("out_details.csv")).unwrap(),
+        common::fixture("speaker_details.expected.csv")
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("out.csv")).unwrap(),
+        common::fixture("speakers_flight.expected.csv")
+    );
+}
+
+#[test]
+fn crawls_partner_detail_pages() {
+    let server = MockServer::start();
+    server
+        .route(
+            "/partners",
+            vec![MockResponse::ok(common::fixture("partners_next_data.html"))],
+        )
+        .route(
+            "/exhibitors/p-0001",
+            vec![MockResponse::ok(common::fixture(
+                "partner_detail_lumen.html",
+            ))],
+        )
+        .route(
+            "/exhibitors/p-0002",
+            vec![MockResponse::ok(common::fixture("partner_detail_db.html"))],
+        );
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "partners",
+            "--url",
+            &server.url("/partners"),
+            "--with-details",
+            "--details-url",
+            &server.url("/exhibitors"),
+            "-o",
+            "out.csv",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("out_details.csv")).unwrap(),
+        common::fixture("partner_details.expected.csv")
+    );
+    assert_eq!(server.requests_to("/exhibitors/p-0003").len(), 1);
+}
+
+#[test]
+fn rejects_details_in_offline_mode() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = common::fixture_path("speakers_flight.html");
+
+    for input_flag in ["--input", "--input-dir"] {
+        let path = if input_flag == "--input" {
+            input.as_path()
+        } else {
+            input.parent().unwrap()
+        };
+        let output = run_cli(
+            dir.path(),
+            &[
+                "speakers",
+                input_flag,
+                path.to_str().unwrap(),
+                "--with-details",
+                "-o",
+                "out.csv",
+            ],
+        );
+
+        assert!(!output.status.success());
+        assert!(
+            stderr(&output).contains("cannot be used with '--with-details'"),
+            "{}",
+            stderr(&output)
+        );
+        assert!(!dir.path().join("out.csv").exists());
+    }
+}
+
+#[test]
+fn lists_partner_categories() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "categories",
+            "--input",
+            common::fixture_path("partners_next_data.html")
+                .to_str()
+                .unwrap(),
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("* startup          8"), "{stdout}");
+    assert!(stdout.contains("  media            1"), "{stdout}");
+}
+
+#[test]
+fn writes_speakers_as_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = common::fixture_path("speakers_flight.html");
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--input",
+            input.to_str().unwrap(),
+            "--format",
+            "json",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    // Without -o the default file name takes the format's extension
+    let json = fs::read_to_string(dir.path().join("vivatech_speakers_2025_extended.json")).unwrap();
+    let speakers: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let zoe = &speakers[1];
+    assert_eq!(zoe["firstname"], "Zoë");
+    assert_eq!(
+        zoe["tags"],
+        serde_json::json!([
+            "Artificial Intelligence",
+            "Climate, Energy & Sustainability"
+        ])
+    );
+    assert_eq!(zoe["is_partner"], true);
+    assert_eq!(zoe["image"], serde_json::Value::Null);
+    assert!(speakers[0]["image"]["u"]
+        .as_str()
+        .is_some_and(|url| url.ends_with("/a7b17528")));
+}
+
+#[test]
+fn writes_partners_as_json_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = common::fixture_path("partners_next_data.html");
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "partners",
+            "--input",
+            input.to_str().unwrap(),
+            "--format",
+            "jsonl",
+            "-o",
+            "out.jsonl",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let lines: Vec<serde_json::Value> = fs::read_to_string(dir.path().join("out.jsonl"))
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[0]["id"], "p-0001");
+    assert_eq!(lines[0]["country_code"], "FR");
+}
+
+#[test]
+fn rejects_spreadsheet_formats_for_sessions_before_scraping() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = common::fixture_path("sessions_flight.html");
+
+    for format in ["parquet", "xlsx"] {
+        let output = run_cli(
+            dir.path(),
+            &[
+                "sessions",
+                "--input",
+                input.to_str().unwrap(),
+                "--format",
+                format,
+            ],
+        );
+
+        assert!(!output.status.success());
+        assert!(stderr(&output).contains("only available for speakers and partners"));
+        assert!(!String::from_utf8_lossy(&output.stdout).contains("Extracting"));
+    }
+}
+
+#[test]
+fn writes_speakers_and_partners_into_one_workbook() {
+    let dir = tempfile::tempdir().unwrap();
+    let pages = dir.path().join("pages");
+    fs::create_dir(&pages).unwrap();
+    for name in ["speakers_flight.html", "partners_next_data.html"] {
+        fs::copy(common::fixture_path(name), pages.join(name)).unwrap();
+    }
+
+    let output = run_cli(dir.path(), &["workbook", "--input-dir", "pages"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let file = fs::File::open(dir.path().join("vivatech_2025.xlsx")).unwrap();
+    let mut archive = zip::ZipArchive::new(file).unwrap();
+    let mut part = |name: &str| {
+        let mut xml = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        xml
+    };
+    let workbook = part("xl/workbook.xml");
+    for sheet in ["Speakers", "Partners", "Summary"] {
+        assert!(workbook.contains(&format!("name=\"{sheet}\"")), "{sheet}");
+    }
+    // The summary counts both entities
+    let strings = part("xl/sharedStrings.xml");
+    assert!(strings.contains("Tencent/AI \\ Lab"));
+    assert!(strings.contains("Switzerland"));
+
+    // One page cannot hold both entities
+    let output = run_cli(
+        dir.path(),
+        &["workbook", "--input", "pages/speakers_flight.html"],
+    );
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("--input-dir"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn spells_out_missing_values_with_null_value() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = common::fixture_path("speakers_flight.html");
+    let input = input.to_str().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--input",
+            input,
+            "--null-value",
+            "N/A",
+            "-o",
+            "out.csv",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let csv = fs::read_to_string(dir.path().join("out.csv")).unwrap();
+    let zoe = csv.lines().nth(2).unwrap();
+    assert!(zoe.contains(",zoe@example.com,"));
+    assert!(zoe.ends_with(",press@fusee.example,N/A,N/A,N/A,N/A"));
+
+    // JSON keeps real nulls whatever --null-value says
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--input",
+            input,
+            "--null-value",
+            "N/A",
+            "--format",
+            "json",
+            "-o",
+            "out.json",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let speakers: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join("out.json")).unwrap()).unwrap();
+    assert_eq!(speakers[0]["email"], serde_json::Value::Null);
+    assert_eq!(
+        speakers[0]["communication_manager"],
+        serde_json::Value::Null
+    );
+    assert_eq!(speakers[2]["image"]["s"], serde_json::Value::Null);
+}
+
+#[test]
+fn spells_out_missing_detail_values_with_null_value() {
+    let server = MockServer::start();
+    server
+        .route(
+            "/speakers",
+            vec![MockResponse::ok(common::fixture("speakers_flight.html"))],
+        )
+        .route(
+            "/speakers/9e3a8589-c629-f011-8b3d-6045bd903b46",
+            vec![MockResponse::ok(common::fixture(
+                "speaker_detail_jensen.html",
+            ))],
+        )
+        .route(
+            "/speakers/55208d04-5e09-f011-aaa7-6045bd9d3cdc",
+            vec![MockResponse::ok(common::fixture("speaker_detail_zoe.html"))],
+        );
+    let dir = tempfile::tempdir().unwrap();
+    let url = server.url("/speakers");
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &url,
+            "--with-details",
+            "--null-value",
+            "N/A",
+            "-o",
+            "out.csv",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let csv = fs::read_to_string(dir.path().join("out_details.csv")).unwrap();
+    assert!(csv.contains("She previously built rockets.\",N/A,https://twitter.com/zoe_fusee,"));
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "speakers",
+            "--url",
+            &url,
+            "--with-details",
+            "--null-value",
+            "N/A",
+            "--format",
+            "json",
+            "-o",
+            "out.json",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let details: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join("out_details.json")).unwrap())
+            .unwrap();
+    assert_eq!(details[1]["linkedin_url"], serde_json::Value::Null);
+}
+
+#[test]
+fn spells_out_missing_session_values_with_null_value() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = common::fixture_path("sessions_flight.html");
+    let input = input.to_str().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "sessions",
+            "--input",
+            input,
+            "--null-value",
+            "N/A",
+            "-o",
+            "out.csv",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let csv = fs::read_to_string(dir.path().join("out.csv")).unwrap();
+    assert_eq!(
+        csv.lines().nth(3).unwrap(),
+        "42,Live from NYC,N/A,N/A,N/A,2025-06-13T10:00:00-04:00,N/A,America/New_York,Livestream,,"
+    );
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "sessions",
+            "--input",
+            input,
+            "--null-value",
+            "N/A",
+            "--format",
+            "json",
+            "-o",
+            "out.json",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let sessions: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join("out.json")).unwrap()).unwrap();
+    for field in ["description", "stage", "room", "end_time"] {
+        assert_eq!(sessions[2][field], serde_json::Value::Null, "{field}");
+    }
+}
+
+#[test]
+fn diffs_two_snapshots() {
+    let dir = tempfile::tempdir().unwrap();
+    let old = dir.path().join("old.csv");
+    let new = dir.path().join("new.csv");
+    let expected = common::fixture("speakers_flight.expected.csv");
+    fs::write(&old, &expected).unwrap();
+    fs::write(&new, expected.replace("Founder and CEO", "CEO")).unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "diff",
+            old.to_str().unwrap(),
+            new.to_str().unwrap(),
+            "--report",
+            "json",
+        ],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    // The report is the whole of stdout, so it can be piped
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report[0]["changed"][0]["changes"][0]["new"], "CEO");
+
+    let output = run_cli(dir.path(), &["diff", old.to_str().unwrap()]);
+    assert!(stderr(&output).contains("<NEW>"), "{}", stderr(&output));
+}
+
+#[test]
+fn rejects_options_of_other_commands() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = common::fixture_path("speakers_flight.html");
+    let input = input.to_str().unwrap();
+
+    for args in [
+        &["speakers", "--input", input, "--cron", "0 * * * *"][..],
+        &["speakers", "--input", input, "--report", "json"],
+        &["diff", "old.csv", "new.csv", "--store", "history"],
+        &[
+            "snapshots",
+            "--store",
+            "history",
+            "--webhook",
+            "http://127.0.0.1/",
+        ],
+        &["categories", "--input", input, "--format", "json"],
+    ] {
+        let output = run_cli(dir.path(), args);
+
+        assert!(!output.status.success(), "{args:?}");
+        assert!(
+            stderr(&output).contains("unexpected argument"),
+            "{}",
+            stderr(&output)
+        );
+    }
+    assert!(!dir.path().join("history").exists());
+}
+
+#[test]
+fn records_scrapes_in_the_store_and_exports_them() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = common::fixture_path("speakers_flight.html");
+    let input = input.to_str().unwrap();
+
+    let output = run_cli(
+        dir.path(),
+        &["speakers", "--input", input, "--store", "history"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Recorded snapshot speakers-"));
+
+    // Scraping the same records again adds nothing to the store
+    let output = run_cli(
+        dir.path(),
+        &["speakers", "--input", input, "--store", "history"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Unchanged since snapshot speakers-"));
+
+    let output = run_cli(dir.path(), &["snapshots", "--store", "history"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let listing = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert_eq!(listing.lines().count(), 1);
+    assert!(listing.contains(input), "{listing}");
+
+    let output = run_cli(
+        dir.path(),
+        &[
+            "export",
+            "latest",
+            "--store",
+            "history",
+            "--format",
+            "json",
+            "-o",
+            "past.json",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let speakers: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join("past.json")).unwrap()).unwrap();
+    assert_eq!(speakers[1]["email"], "zoe@example.com");
+
+    let output = run_cli(dir.path(), &["history", "9e3a8589", "--store", "history"]);
+    assert!(stderr(&output).contains("is in no snapshot"));
+
+    let output = run_cli(dir.path(), &["snapshots"]);
+    assert!(
+        stderr(&output).contains("--store <DIR>"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn notifies_webhooks_of_new_and_changed_records() {
+    let server = MockServer::start();
+    server.route("/hook", vec![MockResponse::ok("")]);
+    server.route("/slack", vec![MockResponse::ok("ok")]);
+    let dir = tempfile::tempdir().unwrap();
+    let html = common::fixture("speakers_flight.html");
+    fs::write(dir.path().join("before.html"), &html).unwrap();
+    fs::write(
+        dir.path().join("after.html"),
+        html.replace("Founder and CEO", "CEO"),
+    )
+    .unwrap();
+
+    let hook = server.url("/hook");
+    let slack = server.url("/slack");
+    let scrape = |input: &str| {
+        run_cli(
+            dir.path(),
+            &[
+                "speakers",
+                "--input",
+                input,
+                "--store",
+                "history",
+                "--webhook",
+                &hook,
+                "--slack-webhook",
+                &slack,
+                "--webhook-secret",
+                "s3cret",
+                "--webhook-template",
+                "{changed} updated {entity}\n{changes}",
+            ],
+        )
+    };
+
+    // The first snapshot is only a baseline
+    let output = scrape("before.html");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(server.requests().is_empty());
+
+    let output = scrape("after.html");
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let [hook_request] = server.requests_to("/hook").try_into().unwrap();
+    assert_eq!(hook_request.method, "POST");
+    assert_eq!(
+        hook_request.header("X-Vivatech-Signature-256"),
+        Some(vivatech::webhook::sign("s3cret", hook_request.body.as_bytes()).as_str())
+    );
+    let payload: serde_json::Value = serde_json::from_str(&hook_request.body).unwrap();
+    assert_eq!(payload["changed"][0]["label"], "Jensen Huang");
+    assert_eq!(payload["added"], serde_json::json!([]));
+
+    let [slack_request] = server.requests_to("/slack").try_into().unwrap();
+    let payload: serde_json::Value = serde_json::from_str(&slack_request.body).unwrap();
+    assert_eq!(
+        payload["text"],
+        "1 updated speakers\n• Updated: Jensen Huang (JobTitle)"
+    );
+}
+
+#[test]
+fn records_nothing_when_the_output_cannot_be_written() {
+    let server = MockServer::start();
+    server.route("/hook", vec![MockResponse::ok("")]);
+    let dir = tempfile::tempdir().unwrap();
+    let html = common::fixture("speakers_flight.html");
+    fs::write(dir.path().join("before.html"), &html).unwrap();
+    fs::write(
+        dir.path().join("after.html"),
+        html.replace("Founder and CEO", "CEO"),
+    )
+    .unwrap();
+
+    let hook = server.url("/hook");
+    let scrape = |input: &str, output_file: &str| {
+        run_cli(
+            dir.path(),
+            &[
+                "speakers",
+                "--input",
+                input,
+                "-o",
+                output_file,
+                "--store",
+                "history",
+                "--webhook",
+                &hook,
+            ],
+        )
+    };
+    let output = scrape("before.html", "out.csv");
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = scrape("after.html", "missing/out.csv");
+    assert!(!output.status.success());
+    let index = fs::read_to_string(dir.path().join("history/index.jsonl")).unwrap();
+    assert_eq!(index.lines().count(), 1);
+    assert!(server.requests().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn watch_records_rounds_and_stops_on_sigterm() {
+    let dir = tempfile::tempdir().unwrap();
+    let pages = dir.path().join("pages");
+    fs::create_dir(&pages).unwrap();
+    let html = common::fixture("speakers_flight.html");
+    let input = pages.join("speakers.html");
+    fs::write(&input, &html).unwrap();
+    fs::copy(
+        common::fixture_path("sessions_flight.html"),
+        pages.join("sessions.html"),
+    )
+    .unwrap();
+
+    // One page would stand for every target
+    let output = run_cli(
+        dir.path(),
+        &[
+            "watch",
+            "--input",
+            "pages/speakers.html",
+            "--store",
+            "history",
+        ],
+    );
+    assert!(
+        stderr(&output).contains("--input-dir"),
+        "{}",
+        stderr(&output)
+    );
+
+    let child = Command::new(env!("CARGO_BIN_EXE_vivatech"))
+        .args(["watch", "--input-dir", "pages", "--store", "history"])
+        .args(["--interval", "1"])
+        .current_dir(dir.path())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let snapshots = |entity: &str| {
+        fs::read_to_string(dir.path().join("history/index.jsonl"))
+            .unwrap_or_default()
+            .matches(&format!(r#""entity":"{entity}""#))
+            .count()
+    };
+    let speaker_snapshots = || snapshots("speakers");
+    let wait_for_snapshots = |count: usize| {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while speaker_snapshots() < count {
+            assert!(Instant::now() < deadline, "no snapshot {count} recorded");
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    };
+
+    // Change the page between two rounds, replacing it in one step
+    wait_for_snapshots(1);
+    let changed = pages.join("changed.tmp");
+    fs::write(&changed, html.replace("Founder and CEO", "CEO")).unwrap();
+    fs::rename(&changed, &input).unwrap();
+    wait_for_snapshots(2);
+
+    let status = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Speakers: 0 added, 0 removed, 1 changed"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains(r#"JobTitle: "Founder and CEO" -> "CEO""#),
+        "{stdout}"
+    );
+    assert!(stdout.contains("🛑 Stopping"), "{stdout}");
+    // Sessions are stored too, once since they never changed
+    assert_eq!(snapshots("sessions"), 1);
+    assert!(
+        stdout.contains("Unchanged since snapshot sessions-"),
+        "{stdout}"
+    );
+    assert!(dir
+        .path()
+        .join("vivatech_speakers_2025_extended.csv")
+        .exists());
+}
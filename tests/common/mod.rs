@@ -0,0 +1,23 @@
+// Shared helpers for the integration tests
+// Each test crate uses a different subset of these helpers.
+#![allow(dead_code)]
+
+pub mod server;
+
+use std::fs;
+use std::path::PathBuf;
+
+pub fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+pub fn fixture(name: &str) -> String {
+    fs::read_to_string(fixture_path(name)).expect("fixture should be readable")
+}
+
+pub fn repo_file(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(name)
+}
@@ -0,0 +1,55 @@
+// Tests for ISO 3166 country resolution
+
+use pretty_assertions::assert_eq;
+use vivatech::countries;
+
+fn alpha2(country: Option<countries::Country>) -> Option<&'static str> {
+    country.map(|country| country.alpha2)
+}
+
+#[test]
+fn table_covers_every_iso_country() {
+    assert_eq!(countries::all().len(), 249);
+    let france = countries::by_code("fra").unwrap();
+    assert_eq!(
+        (france.alpha2, france.alpha3, france.name),
+        ("FR", "FRA", "France")
+    );
+}
+
+#[test]
+fn resolves_names_aliases_and_native_names() {
+    for (text, expected) in [
+        ("France", "FR"),
+        ("  united kingdom ", "GB"),
+        ("UK", "GB"),
+        ("USA", "US"),
+        ("États-Unis", "US"),
+        ("Etats-Unis", "US"),
+        ("Deutschland", "DE"),
+        ("Allemagne", "DE"),
+        ("日本", "JP"),
+        ("Korea, Republic of", "KR"),
+        ("Cote d'Ivoire", "CI"),
+        ("Türkiye", "TR"),
+        ("Turkey", "TR"),
+        ("CHE", "CH"),
+    ] {
+        assert_eq!(alpha2(countries::resolve(text)), Some(expected), "{text}");
+    }
+
+    assert_eq!(countries::resolve("Atlantis"), None);
+    assert_eq!(countries::resolve(""), None);
+}
+
+#[test]
+fn finds_countries_inside_text() {
+    assert_eq!(alpha2(countries::find_in_text("Orange France")), Some("FR"));
+    assert_eq!(
+        alpha2(countries::find_in_text("Bank of Guinea-Bissau")),
+        Some("GW")
+    );
+    // Short codes are too ambiguous inside free text
+    assert_eq!(countries::find_in_text("Talk to us"), None);
+    assert_eq!(countries::find_in_text("UK Robotics"), None);
+}
@@ -0,0 +1,197 @@
+// Tests for comparing export snapshots
+
+mod common;
+
+use pretty_assertions::assert_eq;
+use vivatech::diff::{self, Entity, FieldChange, ReportFormat, Snapshot};
+use vivatech::{output, partners, sessions, speakers, sqlite};
+
+fn fixture_speakers() -> Vec<speakers::Speaker> {
+    speakers::extract_speakers_from_html(&common::fixture("speakers_flight.html")).unwrap()
+}
+
+#[test]
+fn same_data_in_different_formats_has_no_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let csv = dir.path().join("speakers.csv");
+    let jsonl = dir.path().join("speakers.jsonl");
+    let db = dir.path().join("speakers.sqlite");
+
+    let records = speakers::convert_to_csv_records(fixture_speakers());
+    output::write_csv(&records, &csv, "").unwrap();
+    output::write_jsonl(&fixture_speakers(), &jsonl).unwrap();
+    sqlite::write_speakers(&fixture_speakers(), &db).unwrap();
+
+    let from_csv = Snapshot::load(&csv, "").unwrap();
+    for other in [&jsonl, &db] {
+        let diffs = diff::compare(&from_csv, &Snapshot::load(other, "").unwrap());
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].entity, Entity::Speakers);
+        assert!(diffs[0].is_empty(), "{other:?}: {:?}", diffs[0]);
+    }
+}
+
+#[test]
+fn reports_added_removed_and_changed_records() {
+    let old = fixture_speakers();
+    let mut new = fixture_speakers();
+    let removed = new.remove(2);
+    new[0].job_title = "CEO".to_string();
+    new[1].email = None;
+    new[1].id = "new-id".to_string();
+
+    let diffs = diff::compare(
+        &Snapshot::from_speakers(old).unwrap(),
+        &Snapshot::from_speakers(new).unwrap(),
+    );
+    let diff = &diffs[0];
+
+    assert_eq!(
+        diff.added
+            .iter()
+            .map(|r| r.label.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Zoë Dupré"]
+    );
+    assert_eq!(
+        diff.removed
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["55208d04-5e09-f011-aaa7-6045bd9d3cdc", removed.id.as_str()]
+    );
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(
+        diff.changed[0].changes,
+        vec![FieldChange {
+            field: "JobTitle".to_string(),
+            old: "Founder and CEO".to_string(),
+            new: "CEO".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn renders_reports() {
+    let html = common::fixture("partners_next_data.html");
+    let old = partners::extract_partners_from_html(&html).unwrap();
+    let mut new = old.clone();
+    new[0].website = Some("https://a|b.example".to_string());
+    let diffs = diff::compare(
+        &Snapshot::from_partners(old).unwrap(),
+        &Snapshot::from_partners(new).unwrap(),
+    );
+
+    let text = diff::render(&diffs, ReportFormat::Text).unwrap();
+    assert!(text.starts_with("Partners: 0 added, 0 removed, 1 changed\n  ~ .lumen [p-0001]\n"));
+    assert!(text.contains(r#"Website: "https://www.dotlumen.com" -> "https://a|b.example""#));
+
+    let markdown = diff::render(&diffs, ReportFormat::Markdown).unwrap();
+    assert!(markdown.contains("#### .lumen (`p-0001`)"));
+    assert!(markdown.contains("| Website | https://www.dotlumen.com | https://a\\|b.example |"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&diff::render(&diffs, ReportFormat::Json).unwrap()).unwrap();
+    assert_eq!(json[0]["entity"], "partners");
+    assert_eq!(json[0]["changed"][0]["changes"][0]["field"], "Website");
+}
+
+#[test]
+fn missing_values_compare_equal_whatever_their_spelling() {
+    let dir = tempfile::tempdir().unwrap();
+    let current = dir.path().join("current.csv");
+    output::write_csv(
+        &speakers::convert_to_csv_records(fixture_speakers()),
+        &current,
+        "",
+    )
+    .unwrap();
+
+    for (name, null_value) in [("legacy.csv", "N/A"), ("custom.csv", "-")] {
+        let path = dir.path().join(name);
+        let records = speakers::convert_to_csv_records(fixture_speakers());
+        output::write_csv(&records, &path, null_value).unwrap();
+
+        let diffs = diff::compare(
+            &Snapshot::load(&path, "-").unwrap(),
+            &Snapshot::load(&current, "-").unwrap(),
+        );
+        assert!(diffs[0].is_empty(), "{name}: {:?}", diffs[0]);
+    }
+}
+
+#[test]
+fn matches_partners_without_ids_by_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let legacy = dir.path().join("legacy.csv");
+    let current = dir.path().join("current.json");
+
+    let html = common::fixture("partners_next_data.html");
+    let mut partners = partners::extract_partners_from_html(&html).unwrap();
+    output::write_json(&partners, &current).unwrap();
+    partners.iter_mut().for_each(|partner| partner.id = None);
+    let records = partners::convert_to_partner_records(partners);
+    output::write_csv(&records, &legacy, "N/A").unwrap();
+
+    let diffs = diff::compare(
+        &Snapshot::load(&legacy, "").unwrap(),
+        &Snapshot::load(&current, "").unwrap(),
+    );
+    assert_eq!(diffs.len(), 1);
+    assert!(diffs[0].is_empty(), "{:?}", diffs[0]);
+}
+
+#[test]
+fn empty_export_takes_the_other_side_entity() {
+    let dir = tempfile::tempdir().unwrap();
+    let empty = dir.path().join("empty.json");
+    let full = dir.path().join("full.json");
+    output::write_json::<speakers::Speaker>(&[], &empty).unwrap();
+    output::write_json(&fixture_speakers(), &full).unwrap();
+    let (empty, full) = (
+        Snapshot::load(&empty, "").unwrap(),
+        Snapshot::load(&full, "").unwrap(),
+    );
+
+    let diffs = diff::compare(&empty, &full);
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].entity, Entity::Speakers);
+    assert_eq!(diffs[0].added.len(), 3);
+
+    let diffs = diff::compare(&full, &empty);
+    assert_eq!(diffs[0].removed.len(), 3);
+    assert!(diffs[0].added.is_empty());
+}
+
+#[test]
+fn compares_sessions_across_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    let csv = dir.path().join("sessions.csv");
+    let json = dir.path().join("sessions.json");
+    let db = dir.path().join("sessions.sqlite");
+    let fixture_sessions =
+        || sessions::extract_sessions_from_html(&common::fixture("sessions_flight.html")).unwrap();
+
+    output::write_csv(
+        &sessions::convert_to_session_records(fixture_sessions()),
+        &csv,
+        "",
+    )
+    .unwrap();
+    output::write_json(&fixture_sessions(), &json).unwrap();
+    sqlite::write_sessions(&fixture_sessions(), &db).unwrap();
+
+    let from_csv = Snapshot::load(&csv, "").unwrap();
+    for other in [&json, &db] {
+        let diffs = diff::compare(&from_csv, &Snapshot::load(other, "").unwrap());
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].entity, Entity::Sessions);
+        assert!(diffs[0].is_empty(), "{other:?}: {:?}", diffs[0]);
+    }
+
+    let mut moved = fixture_sessions();
+    moved[0].room = Some("Hall 2".to_string());
+    let diffs = diff::compare(&from_csv, &Snapshot::from_sessions(moved).unwrap());
+    assert_eq!(diffs[0].changed[0].label, "Opening Keynote");
+    assert_eq!(diffs[0].changed[0].changes[0].field, "Room");
+}
@@ -0,0 +1,39 @@
+// Tests for the fetch retry policy
+
+use std::time::Duration;
+use vivatech::fetch::RetryPolicy;
+
+#[test]
+fn backoff_grows_exponentially_with_jitter() {
+    let policy = RetryPolicy {
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_secs(10),
+        ..RetryPolicy::default()
+    };
+
+    for retry in 0..5 {
+        let step = Duration::from_millis(100 * 2_u64.pow(retry));
+        for _ in 0..20 {
+            let delay = policy.backoff(retry);
+            assert!(delay >= step / 2, "{delay:?} below {step:?} / 2");
+            assert!(delay <= step, "{delay:?} above {step:?}");
+        }
+    }
+}
+
+#[test]
+fn backoff_is_capped() {
+    let policy = RetryPolicy {
+        base_delay: Duration::from_secs(1),
+        max_delay: Duration::from_secs(5),
+        ..RetryPolicy::default()
+    };
+
+    assert!(policy.backoff(10) <= Duration::from_secs(5));
+    assert!(policy.backoff(u32::MAX) <= Duration::from_secs(5));
+}
+
+#[test]
+fn none_disables_retries() {
+    assert_eq!(RetryPolicy::none().max_retries, 0);
+}
@@ -0,0 +1,53 @@
+// Tests for the output formats shared by every scraped type
+
+mod common;
+
+use serde_json::Value;
+use vivatech::{partners, sessions, speakers};
+
+// Every object key in `value`, however deeply nested
+fn keys(value: &Value) -> Vec<String> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .flat_map(|(key, value)| std::iter::once(key.clone()).chain(keys(value)))
+            .collect(),
+        Value::Array(items) => items.iter().flat_map(keys).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[test]
+fn json_keys_are_snake_case_for_every_type() {
+    let speakers =
+        speakers::extract_speakers_from_html(&common::fixture("speakers_flight.html")).unwrap();
+    let partners =
+        partners::extract_partners_from_html(&common::fixture("partners_next_data.html")).unwrap();
+    let sessions =
+        sessions::extract_sessions_from_html(&common::fixture("sessions_flight.html")).unwrap();
+
+    for value in [
+        serde_json::to_value(&speakers).unwrap(),
+        serde_json::to_value(&partners).unwrap(),
+        serde_json::to_value(&sessions).unwrap(),
+    ] {
+        let keys = keys(&value);
+        assert!(!keys.is_empty());
+        for key in keys {
+            assert!(
+                key.chars().all(|c| c.is_ascii_lowercase() || c == '_'),
+                "{key}"
+            );
+        }
+    }
+    let speaker = serde_json::to_value(&speakers[0]).unwrap();
+    assert!(speaker.get("job_title").is_some());
+    assert!(speaker.get("has_bio").is_some());
+
+    // Snapshots written with the site's camelCase names still read back
+    let legacy = serde_json::json!({"id": "s-1", "firstname": "Ada", "lastname": "Lovelace",
+        "jobTitle": "Analyst", "company": "Engines", "isPartner": true});
+    let speaker: speakers::Speaker = serde_json::from_value(legacy).unwrap();
+    assert_eq!(speaker.job_title, "Analyst");
+    assert!(speaker.is_partner);
+}
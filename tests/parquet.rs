@@ -0,0 +1,78 @@
+// Tests for the typed Parquet export
+
+mod common;
+
+use arrow_array::cast::AsArray;
+use arrow_array::{Array, RecordBatch};
+use arrow_schema::DataType;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use pretty_assertions::assert_eq;
+use std::fs::File;
+use std::path::Path;
+use vivatech::{partners, speakers};
+
+fn read_batch(path: &Path) -> RecordBatch {
+    let mut reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    reader.next().unwrap().unwrap()
+}
+
+#[test]
+fn writes_typed_speaker_columns() {
+    let speakers =
+        speakers::extract_speakers_from_html(&common::fixture("speakers_flight.html")).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("speakers.parquet");
+
+    vivatech::parquet::write_speakers(&speakers, &path).unwrap();
+    let batch = read_batch(&path);
+
+    assert_eq!(batch.num_rows(), 3);
+    let schema = batch.schema();
+    assert_eq!(
+        schema.field_with_name("HasBio").unwrap().data_type(),
+        &DataType::Boolean
+    );
+    assert!(matches!(
+        schema.field_with_name("Tags").unwrap().data_type(),
+        DataType::List(_)
+    ));
+    assert!(!schema.field_with_name("ID").unwrap().is_nullable());
+
+    let has_bio = batch.column_by_name("HasBio").unwrap().as_boolean();
+    assert_eq!(
+        (0..3).map(|row| has_bio.value(row)).collect::<Vec<_>>(),
+        vec![true, false, true]
+    );
+    let tags = batch.column_by_name("Tags").unwrap().as_list::<i32>();
+    assert_eq!(tags.value(1).len(), 2);
+    assert_eq!(
+        tags.value(1).as_string::<i32>().value(1),
+        "Climate, Energy & Sustainability"
+    );
+
+    // Missing values are nulls, not "N/A"
+    let manager = batch.column_by_name("CommunicationManager").unwrap();
+    assert!(manager.is_null(0));
+    assert_eq!(manager.as_string::<i32>().value(1), "press@fusee.example");
+    assert!(batch.column_by_name("ImageMainURL").unwrap().is_null(1));
+}
+
+#[test]
+fn writes_partners_with_nullable_columns() {
+    let partners =
+        partners::extract_partners_from_html(&common::fixture("partners_next_data.html")).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("partners.parquet");
+
+    vivatech::parquet::write_partners(&partners, &path).unwrap();
+    let batch = read_batch(&path);
+
+    assert_eq!(batch.num_rows(), 11);
+    let country_code = batch.column_by_name("CountryCode").unwrap();
+    assert_eq!(country_code.as_string::<i32>().value(0), "FR");
+    // p-0006 has no known country
+    assert!(country_code.is_null(3));
+}
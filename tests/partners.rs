@@ -0,0 +1,407 @@
+// Regression tests for partner extraction against trimmed page fixtures
+
+mod common;
+
+use pretty_assertions::assert_eq;
+use std::fs;
+use vivatech::output;
+use vivatech::partners::{self, CategoryFilter, PartnerRecord, TeamMember};
+
+fn records_from_fixture(name: &str) -> Vec<PartnerRecord> {
+    let partners = partners::extract_partners_from_html(&common::fixture(name))
+        .expect("fixture should contain partners");
+    partners::convert_to_partner_records(partners)
+}
+
+fn record(
+    id: &str,
+    company_name: &str,
+    category: &str,
+    (country, country_code, region): (&str, &str, &str),
+    description: &str,
+    website: &str,
+    logo_url: &str,
+) -> PartnerRecord {
+    // Empty strings stand for missing values
+    let optional = |value: &str| Some(value.to_string()).filter(|value| !value.is_empty());
+    PartnerRecord {
+        id: optional(id),
+        company_name: company_name.to_string(),
+        category: category.to_string(),
+        country: optional(country),
+        country_code: optional(country_code),
+        region: optional(region),
+        description: optional(description),
+        website: optional(website),
+        logo_url: optional(logo_url),
+    }
+}
+
+#[test]
+fn extracts_partners_from_next_data() {
+    let records = records_from_fixture("partners_next_data.html");
+
+    assert_eq!(
+        records,
+        vec![
+            record(
+                "p-0001",
+                ".lumen",
+                "startup",
+                ("France", "FR", "Île-de-France"),
+                ".lumen builds pedestrian autonomous driving AI.",
+                "https://www.dotlumen.com",
+                "https://cdn.example/lumen.png",
+            ),
+            record(
+                "p-0002",
+                "Deutsche Bahn",
+                "premium partner",
+                ("Germany", "DE", "Berlin"),
+                "Mobility for Germany 🚆",
+                "https://db.de",
+                "",
+            ),
+            record(
+                "p-0003",
+                "Kyoto Robotics - Japan",
+                "startup",
+                ("Japan", "JP", ""),
+                "Vision for robots, \"pick & place\".",
+                "",
+                "",
+            ),
+            record(
+                "p-0006",
+                ".lumen",
+                "startup",
+                ("", "", ""),
+                "Lighting studio sharing a name with another startup",
+                "",
+                "",
+            ),
+            record(
+                "p-0007",
+                "北京 AI Co",
+                "partner",
+                ("China", "CN", "Beijing"),
+                "",
+                "",
+                ""
+            ),
+            record(
+                "p-0008",
+                "Zürich Quantum",
+                "startup",
+                ("Switzerland", "CH", "Zurich"),
+                "",
+                "",
+                "",
+            ),
+            record(
+                "p-0009",
+                "Nordic Grid",
+                "partner",
+                ("Norway", "NO", "Oslo"),
+                "",
+                "",
+                ""
+            ),
+            record(
+                "p-0010",
+                "Hanbit Labs",
+                "startup",
+                ("South Korea", "KR", ""),
+                "Founded in Paris, scaling in Seoul.",
+                "",
+                "",
+            ),
+            // "Paris, Texas" is not Paris, France
+            record(
+                "p-0011",
+                "Lone Star Analytics",
+                "startup",
+                ("United States", "US", "Texas"),
+                "",
+                "",
+                "",
+            ),
+            // Cambridge alone is ambiguous and left unresolved
+            record("p-0012", "Fenland Bio", "startup", ("", "", ""), "", "", ""),
+            record(
+                "p-0013",
+                "Backbay Robotics",
+                "startup",
+                ("United States", "US", "Massachusetts"),
+                "",
+                "",
+                "",
+            ),
+        ]
+    );
+}
+
+#[test]
+fn reports_missing_partner_data() {
+    assert!(partners::extract_partners_from_html(&common::fixture("no_data.html")).is_err());
+}
+
+#[test]
+fn partner_csv_matches_golden_file() {
+    let records = records_from_fixture("partners_next_data.html");
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("partners.csv");
+
+    output::write_csv(&records, &output, "").unwrap();
+
+    assert_eq!(
+        fs::read_to_string(output).unwrap(),
+        common::fixture("partners_next_data.expected.csv")
+    );
+}
+
+#[test]
+fn committed_partners_csv_round_trips() {
+    // The committed export predates the ID column, which reads back as empty
+    let committed = common::repo_file("vivatech_partners_2025.csv");
+    let records: Vec<PartnerRecord> = csv::Reader::from_path(&committed)
+        .unwrap()
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(records.len() > 2800);
+    assert!(records.iter().all(|record| record.id.is_none()));
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("partners.csv");
+    output::write_csv(&records, &output, "").unwrap();
+
+    assert!(fs::read_to_string(&output)
+        .unwrap()
+        .starts_with("ID,CompanyName,Category,"));
+    let reread: Vec<PartnerRecord> = csv::Reader::from_path(&output)
+        .unwrap()
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(reread, records);
+}
+
+#[test]
+fn null_value_never_fills_a_missing_id() {
+    let html = common::fixture("partners_next_data.html");
+    let mut partners = partners::extract_partners_from_html(&html).unwrap();
+    partners[0].id = None;
+    partners[0].website = None;
+    let records = partners::convert_to_partner_records(partners);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("partners.csv");
+    output::write_csv(&records, &path, "N/A").unwrap();
+
+    let reread: Vec<PartnerRecord> = csv::Reader::from_path(&path)
+        .unwrap()
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(reread[0].id, None);
+    assert_eq!(reread[0].website.as_deref(), Some("N/A"));
+}
+
+#[test]
+fn finds_countries_only_in_location_fields() {
+    let exhibitors = serde_json::json!({"exhibitors": [
+        {"id": "x-1", "name": "Jordan Ventures", "type": "startup",
+         "desc": "Backed by Chad and Georgia funds"},
+        {"id": "x-2", "name": "Dubai Holdings", "type": "partner"},
+        {"id": "x-3", "name": "Oasis Labs", "type": "startup",
+         "key_figures": {"city": "Dubai"}},
+        {"id": "x-4", "name": "Fjord AI", "type": "startup",
+         "key_figures": {"city": "Smallville, Norway"}},
+        {"id": "x-5", "name": "Atlas Robotics - Morocco", "type": "startup"}
+    ]});
+    let html =
+        format!(r#"<script id="__NEXT_DATA__" type="application/json">{exhibitors}</script>"#);
+
+    let countries: Vec<Option<String>> = partners::extract_partners_from_html(&html)
+        .unwrap()
+        .into_iter()
+        .map(|partner| partner.country_code)
+        .collect();
+    assert_eq!(
+        countries,
+        vec![
+            None,
+            None,
+            Some("AE".to_string()),
+            Some("NO".to_string()),
+            Some("MA".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn structured_country_picks_among_homonyms_of_a_large_city() {
+    let exhibitors = serde_json::json!({"exhibitors": [
+        {"id": "x-1", "name": "Maple Labs", "type": "startup",
+         "key_figures": {"city": "London", "country": "Canada"}},
+        {"id": "x-2", "name": "Thames AI", "type": "startup",
+         "key_figures": {"city": "London"}}
+    ]});
+    let html =
+        format!(r#"<script id="__NEXT_DATA__" type="application/json">{exhibitors}</script>"#);
+
+    let places: Vec<(Option<String>, Option<String>)> = partners::extract_partners_from_html(&html)
+        .unwrap()
+        .into_iter()
+        .map(|partner| (partner.country_code, partner.region))
+        .collect();
+    assert_eq!(
+        places,
+        vec![
+            (Some("CA".to_string()), Some("Ontario".to_string())),
+            (Some("GB".to_string()), Some("England".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn extracts_partner_details_from_flight_payload() {
+    let details = partners::extract_partner_details_from_html(
+        &common::fixture("partner_detail_lumen.html"),
+        "p-0001",
+    )
+    .unwrap();
+
+    assert_eq!(details.booth, Some("B12".to_string()));
+    assert_eq!(details.hall, Some("Hall 1".to_string()));
+    assert_eq!(details.sectors, vec!["Mobility", "Artificial Intelligence"]);
+    assert_eq!(details.funding_stage, Some("Seed".to_string()));
+    assert_eq!(details.employee_count, Some("45".to_string()));
+    assert_eq!(details.founding_year, Some("2016".to_string()));
+    // The CEO's own profile must not be mistaken for the company page
+    assert_eq!(
+        details.linkedin_url,
+        Some("https://www.linkedin.com/company/dotlumen".to_string())
+    );
+    assert_eq!(
+        details.twitter_url,
+        Some("https://x.com/dotlumen".to_string())
+    );
+    assert_eq!(
+        details.team_members,
+        vec![
+            TeamMember {
+                name: "Cornel Amariei".to_string(),
+                job_title: Some("CEO".to_string()),
+            },
+            TeamMember {
+                name: "Ana Pop".to_string(),
+                job_title: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn ignores_team_member_links_without_a_company_profile() {
+    let details = partners::extract_partner_details_from_html(
+        &common::fixture("partner_detail_kyoto.html"),
+        "p-0003",
+    )
+    .unwrap();
+
+    // Only the team member and VivaTech have a LinkedIn page here
+    assert_eq!(details.linkedin_url, None);
+    assert_eq!(
+        details.twitter_url,
+        Some("https://x.com/kyoto_robotics".to_string())
+    );
+    assert_eq!(details.team_members.len(), 1);
+}
+
+#[test]
+fn ignores_links_of_any_nested_object() {
+    let partner = serde_json::json!({"partner": {
+        "id": "p-0009", "name": "Nordlys", "type": "startup",
+        "founders": [{"name": "Eva Lund", "linkedin": "https://www.linkedin.com/in/evalund"}],
+        "parentCompany": {"name": "Nordlys Group", "twitter": "@nordlysgroup"},
+        "socialNetworks": {"twitter": "@nordlys"}
+    }});
+    let html = format!(r#"<script id="__NEXT_DATA__" type="application/json">{partner}</script>"#);
+
+    let details = partners::extract_partner_details_from_html(&html, "p-0009").unwrap();
+    assert_eq!(details.linkedin_url, None);
+    assert_eq!(
+        details.twitter_url,
+        Some("https://x.com/nordlys".to_string())
+    );
+}
+
+#[test]
+fn partner_details_csv_matches_golden_file() {
+    let details = ["partner_detail_lumen.html", "partner_detail_db.html"]
+        .iter()
+        .zip(["p-0001", "p-0002"])
+        .map(|(name, id)| {
+            partners::extract_partner_details_from_html(&common::fixture(name), id).unwrap()
+        })
+        .collect();
+    let records = partners::convert_to_detail_records(details);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("partner_details.csv");
+    output::write_csv(&records, &path, "").unwrap();
+
+    assert_eq!(
+        fs::read_to_string(path).unwrap(),
+        common::fixture("partner_details.expected.csv")
+    );
+}
+
+#[test]
+fn filters_partners_by_category() {
+    let html = common::fixture("partners_next_data.html");
+    let names = |values: &[&str]| -> Vec<String> {
+        let values: Vec<String> = values.iter().map(ToString::to_string).collect();
+        partners::extract_partners_with_filter(&html, &CategoryFilter::from_values(&values))
+            .unwrap()
+            .into_iter()
+            .map(|partner| partner.id.unwrap_or_default())
+            .collect()
+    };
+
+    assert_eq!(
+        names(&[]),
+        vec![
+            "p-0001", "p-0002", "p-0003", "p-0006", "p-0007", "p-0008", "p-0009", "p-0010",
+            "p-0011", "p-0012", "p-0013"
+        ]
+    );
+    assert_eq!(
+        names(&["Media", "partner"]),
+        vec!["p-0004", "p-0007", "p-0009"]
+    );
+    assert_eq!(
+        names(&["startup", "all"]),
+        vec![
+            "p-0001", "p-0002", "p-0003", "p-0004", "p-0006", "p-0007", "p-0008", "p-0009",
+            "p-0010", "p-0011", "p-0012", "p-0013"
+        ]
+    );
+}
+
+#[test]
+fn counts_partner_categories() {
+    let counts = partners::count_categories(&common::fixture("partners_next_data.html")).unwrap();
+
+    assert_eq!(
+        counts,
+        vec![
+            ("startup".to_string(), 8),
+            ("partner".to_string(), 2),
+            ("premium partner".to_string(), 2),
+            ("media".to_string(), 1),
+        ]
+    );
+}
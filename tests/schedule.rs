@@ -0,0 +1,44 @@
+// Tests for watch mode schedules
+
+use chrono::{TimeZone, Utc};
+use pretty_assertions::assert_eq;
+use std::time::Duration;
+use vivatech::schedule::Schedule;
+
+#[test]
+fn interval_rounds_start_a_fixed_time_apart() {
+    let start = Utc.with_ymd_and_hms(2025, 6, 11, 9, 30, 0).unwrap();
+    let schedule = Schedule::interval(Duration::from_secs(900)).unwrap();
+    assert_eq!(
+        schedule.next_run(start).unwrap(),
+        Utc.with_ymd_and_hms(2025, 6, 11, 9, 45, 0).unwrap()
+    );
+    assert!(Schedule::interval(Duration::ZERO).is_err());
+}
+
+#[test]
+fn cron_rounds_follow_the_expression() {
+    let start = Utc.with_ymd_and_hms(2025, 6, 11, 9, 30, 0).unwrap();
+    let every_six_hours = Schedule::cron("0 */6 * * *").unwrap();
+    assert_eq!(
+        every_six_hours.next_run(start).unwrap(),
+        Utc.with_ymd_and_hms(2025, 6, 11, 12, 0, 0).unwrap()
+    );
+
+    // A round started on a slot waits for the next one
+    let on_the_hour = Schedule::cron("0 * * * *").unwrap();
+    let slot = Utc.with_ymd_and_hms(2025, 6, 11, 10, 0, 0).unwrap();
+    assert_eq!(
+        on_the_hour.next_run(slot).unwrap(),
+        Utc.with_ymd_and_hms(2025, 6, 11, 11, 0, 0).unwrap()
+    );
+
+    // Six fields start with seconds
+    let every_ten_seconds = Schedule::cron("*/10 * * * * *").unwrap();
+    assert_eq!(
+        every_ten_seconds.next_run(start).unwrap(),
+        Utc.with_ymd_and_hms(2025, 6, 11, 9, 30, 10).unwrap()
+    );
+
+    assert!(Schedule::cron("every hour").is_err());
+}
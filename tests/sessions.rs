@@ -0,0 +1,116 @@
+// Regression tests for session extraction against trimmed page fixtures
+
+mod common;
+
+use pretty_assertions::assert_eq;
+use std::fs;
+use vivatech::output;
+use vivatech::sessions::{self, SessionRecord};
+
+fn records_from_fixture(name: &str) -> Vec<SessionRecord> {
+    let sessions = sessions::extract_sessions_from_html(&common::fixture(name))
+        .expect("fixture should contain sessions");
+    sessions::convert_to_session_records(sessions)
+}
+
+#[test]
+fn extracts_sessions_from_flight_payload() {
+    let records = records_from_fixture("sessions_flight.html");
+
+    assert_eq!(
+        records[0],
+        SessionRecord {
+            id: "sess-001".to_string(),
+            title: "Opening Keynote".to_string(),
+            description: Some("AI & the next decade 🚀".to_string()),
+            stage: Some("Dome".to_string()),
+            room: Some("Hall 1".to_string()),
+            start_time: Some("2025-06-11T09:00:00+02:00".to_string()),
+            end_time: Some("2025-06-11T09:45:00+02:00".to_string()),
+            timezone: "Europe/Paris".to_string(),
+            format: Some("Keynote".to_string()),
+            themes: "Artificial Intelligence, Future of Work".to_string(),
+            speaker_ids: "9e3a8589-c629-f011-8b3d-6045bd903b46, \
+                          55208d04-5e09-f011-aaa7-6045bd9d3cdc"
+                .to_string(),
+        }
+    );
+    // Sessions without a start time are not agenda entries
+    assert_eq!(records.len(), 3);
+}
+
+#[test]
+fn localises_times_without_offset() {
+    let sessions =
+        sessions::extract_sessions_from_html(&common::fixture("sessions_flight.html")).unwrap();
+
+    let paris = &sessions[1];
+    assert_eq!(
+        paris.start_time.unwrap().to_rfc3339(),
+        "2025-06-12T14:30:00+02:00"
+    );
+    assert_eq!(paris.timezone, "Europe/Paris");
+
+    let new_york = &sessions[2];
+    assert_eq!(new_york.id, "42");
+    assert_eq!(
+        new_york.start_time.unwrap().to_rfc3339(),
+        "2025-06-13T10:00:00-04:00"
+    );
+    assert_eq!(new_york.end_time, None);
+    assert_eq!(new_york.timezone, "America/New_York");
+}
+
+// Page pushing `payload` as a single flight chunk
+fn flight_page(payload: &str) -> String {
+    let chunk = serde_json::json!([1, payload]);
+    format!("<script>self.__next_f.push({chunk})</script>")
+}
+
+#[test]
+fn decodes_escaped_dollar_strings() {
+    let html = flight_page(
+        "0:{\"sessions\":[{\"id\":\"sess-009\",\"title\":\"$$5M prize pitch\",\
+         \"startDate\":\"2025-06-11T09:00:00+02:00\"}]}\n",
+    );
+
+    let sessions = sessions::extract_sessions_from_html(&html).unwrap();
+    assert_eq!(sessions[0].title, "$5M prize pitch");
+}
+
+#[test]
+fn parses_flight_dates() {
+    let html = flight_page(
+        "0:{\"sessions\":[{\"id\":\"sess-009\",\"title\":\"Pitch\",\
+         \"startDate\":\"$D2025-06-11T07:00:00.000Z\",\"endDate\":\"$D2025-06-11T07:30:00.000Z\"}]}\n",
+    );
+
+    let sessions = sessions::extract_sessions_from_html(&html).unwrap();
+    assert_eq!(
+        sessions[0].start_time.unwrap().to_rfc3339(),
+        "2025-06-11T07:00:00+00:00"
+    );
+    assert_eq!(
+        sessions[0].end_time.unwrap().to_rfc3339(),
+        "2025-06-11T07:30:00+00:00"
+    );
+}
+
+#[test]
+fn reports_missing_session_data() {
+    assert!(sessions::extract_sessions_from_html(&common::fixture("no_data.html")).is_err());
+}
+
+#[test]
+fn session_csv_matches_golden_file() {
+    let records = records_from_fixture("sessions_flight.html");
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("sessions.csv");
+
+    output::write_csv(&records, &output, "").unwrap();
+
+    assert_eq!(
+        fs::read_to_string(output).unwrap(),
+        common::fixture("sessions_flight.expected.csv")
+    );
+}
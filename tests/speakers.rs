@@ -0,0 +1,252 @@
+// Regression tests for speaker extraction against trimmed page fixtures
+
+mod common;
+
+use pretty_assertions::assert_eq;
+use std::fs;
+use vivatech::output;
+use vivatech::speakers::{self, SpeakerRecord};
+
+const IMG: &str =
+    "https://storageprdv2inwink.blob.core.windows.net/eb8f3fce-49cf-458a-94c3-1e14a570d54c/";
+
+fn records_from_fixture(name: &str) -> Vec<SpeakerRecord> {
+    let speakers = speakers::extract_speakers_from_html(&common::fixture(name))
+        .expect("fixture should contain speakers");
+    speakers::convert_to_csv_records(speakers)
+}
+
+#[test]
+fn extracts_speakers_from_flight_payload() {
+    let records = records_from_fixture("speakers_flight.html");
+
+    assert_eq!(
+        records,
+        vec![
+            SpeakerRecord {
+                id: "9e3a8589-c629-f011-8b3d-6045bd903b46".to_string(),
+                first_name: "Jensen".to_string(),
+                last_name: "Huang".to_string(),
+                email: None,
+                job_title: "Founder and CEO".to_string(),
+                company: "NVIDIA".to_string(),
+                tags: String::new(),
+                themes: String::new(),
+                has_bio: true,
+                has_sessions: true,
+                is_official: true,
+                is_partner: false,
+                is_top_speaker: true,
+                communication_manager: None,
+                image_small_url: Some(format!("{IMG}73b9db4a")),
+                image_thumbnail_url: Some(format!("{IMG}282803d3")),
+                image_large_url: Some(format!("{IMG}f361626e")),
+                image_main_url: Some(format!("{IMG}a7b17528")),
+            },
+            SpeakerRecord {
+                id: "55208d04-5e09-f011-aaa7-6045bd9d3cdc".to_string(),
+                first_name: "Zoë".to_string(),
+                last_name: "Dupré".to_string(),
+                email: Some("zoe@example.com".to_string()),
+                job_title: "CEO & Co-founder".to_string(),
+                company: "Fusée 🚀 Labs".to_string(),
+                tags: "Artificial Intelligence, Climate, Energy & Sustainability".to_string(),
+                themes: "Future of Work, Startups".to_string(),
+                has_bio: false,
+                has_sessions: false,
+                is_official: true,
+                is_partner: true,
+                is_top_speaker: false,
+                communication_manager: Some("press@fusee.example".to_string()),
+                image_small_url: None,
+                image_thumbnail_url: None,
+                image_large_url: None,
+                image_main_url: None,
+            },
+            SpeakerRecord {
+                id: "a1b2c3d4-0000-f011-aaa7-6045bd9d3cdc".to_string(),
+                first_name: "𠀀 Wei".to_string(),
+                last_name: "张".to_string(),
+                email: None,
+                job_title: "Head of \"Research\"".to_string(),
+                company: "Tencent/AI \\ Lab".to_string(),
+                tags: "Robotics".to_string(),
+                themes: String::new(),
+                has_bio: true,
+                has_sessions: false,
+                is_official: false,
+                is_partner: false,
+                is_top_speaker: false,
+                communication_manager: None,
+                image_small_url: None,
+                image_thumbnail_url: None,
+                image_large_url: None,
+                image_main_url: Some(format!("{IMG}only-main")),
+            },
+        ]
+    );
+}
+
+#[test]
+fn extracts_speakers_from_escaped_script_string() {
+    let records = records_from_fixture("speakers_escaped.html");
+
+    let names: Vec<(&str, &str, &str)> = records
+        .iter()
+        .map(|r| {
+            (
+                r.first_name.as_str(),
+                r.last_name.as_str(),
+                r.company.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("Arthur", "Mensch", "Mistral AI"),
+            ("Émilie", "O'Brien", "Café [Beta]"),
+        ]
+    );
+    assert_eq!(records[0].image_main_url.as_deref(), Some("u.png"));
+    assert!(records[0].is_top_speaker);
+    assert_eq!(records[1].tags, "Mobility, Health & Wellness");
+    assert_eq!(records[1].image_main_url, None);
+}
+
+#[test]
+fn finds_complete_arrays_inside_broken_json() {
+    let html = r#"<script>window.state = {"speakers": [{"id": "1", "firstname": "Ada",
+        "lastname": "Lovelace", "jobTitle": "Analyst", "company": "Engines"}], "next": </script>"#;
+
+    let speakers = speakers::extract_speakers_from_html(html).unwrap();
+    assert_eq!(speakers.len(), 1);
+    assert_eq!(speakers[0].lastname, "Lovelace");
+}
+
+#[test]
+fn skips_unterminated_json_in_linear_time() {
+    // Every nested candidate used to be rescanned up to the end of the broken one
+    let unterminated = r#"{"a": "#.repeat(50_000);
+    let html = format!(
+        "<p>{unterminated}</p>{}",
+        common::fixture("speakers_escaped.html")
+    );
+
+    let speakers = speakers::extract_speakers_from_html(&html).unwrap();
+    assert_eq!(speakers.len(), 2);
+}
+
+#[test]
+fn reports_missing_speaker_data() {
+    let err = speakers::extract_speakers_from_html(&common::fixture("no_data.html"))
+        .expect_err("page without data should fail");
+    assert!(err.to_string().contains("Could not find speaker data"));
+}
+
+#[test]
+fn speaker_csv_matches_golden_file() {
+    let records = records_from_fixture("speakers_flight.html");
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("speakers.csv");
+
+    output::write_csv(&records, &output, "").unwrap();
+
+    assert_eq!(
+        fs::read_to_string(output).unwrap(),
+        common::fixture("speakers_flight.expected.csv")
+    );
+}
+
+#[test]
+fn committed_speakers_csv_round_trips() {
+    let committed = common::repo_file("vivatech_speakers_2025_extended.csv");
+    let records: Vec<SpeakerRecord> = csv::Reader::from_path(&committed)
+        .unwrap()
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(records.len() > 700);
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("speakers.csv");
+    output::write_csv(&records, &output, "").unwrap();
+
+    assert_eq!(
+        fs::read_to_string(output).unwrap(),
+        fs::read_to_string(committed).unwrap()
+    );
+}
+
+#[test]
+fn extracts_speaker_details_from_flight_payload() {
+    let details = speakers::extract_speaker_details_from_html(
+        &common::fixture("speaker_detail_jensen.html"),
+        "9e3a8589-c629-f011-8b3d-6045bd903b46",
+    )
+    .unwrap();
+
+    assert_eq!(
+        details.biography,
+        Some("Jensen Huang founded NVIDIA in 1993 & has served as CEO since.\nHe holds a BSEE degree.".to_string())
+    );
+    assert_eq!(
+        details.linkedin_url,
+        Some("https://www.linkedin.com/in/jenhsunhuang".to_string())
+    );
+    assert_eq!(
+        details.twitter_url,
+        Some("https://x.com/nvidia".to_string())
+    );
+    assert_eq!(details.nationality, Some("American".to_string()));
+    assert_eq!(details.session_ids, vec!["sess-001".to_string()]);
+}
+
+#[test]
+fn extracts_speaker_details_from_next_data() {
+    let details = speakers::extract_speaker_details_from_html(
+        &common::fixture("speaker_detail_zoe.html"),
+        "55208d04-5e09-f011-aaa7-6045bd9d3cdc",
+    )
+    .unwrap();
+
+    assert_eq!(
+        details.biography,
+        Some("Zoë leads Fusée 🚀 Labs.\nShe previously built rockets.".to_string())
+    );
+    // Links only found in the page markup are not attributed to the speaker
+    assert_eq!(details.linkedin_url, None);
+    assert_eq!(
+        details.twitter_url,
+        Some("https://twitter.com/zoe_fusee".to_string())
+    );
+    // A country field is where the speaker is based, not their nationality
+    assert_eq!(details.nationality, None);
+    assert_eq!(details.session_ids, vec!["sess-001".to_string()]);
+}
+
+#[test]
+fn ignores_links_of_nested_company_and_session_objects() {
+    let details = speakers::extract_speaker_details_from_html(
+        &common::fixture("speaker_detail_ada.html"),
+        "sp-ada",
+    )
+    .unwrap();
+
+    assert_eq!(
+        details.linkedin_url,
+        Some("https://www.linkedin.com/in/ab".to_string())
+    );
+    assert_eq!(details.twitter_url, None);
+    assert_eq!(details.nationality, None);
+    assert_eq!(details.session_ids, vec!["sess-002".to_string()]);
+}
+
+#[test]
+fn reports_missing_speaker_details() {
+    assert!(speakers::extract_speaker_details_from_html(
+        &common::fixture("speaker_detail_zoe.html"),
+        "unknown-speaker",
+    )
+    .is_err());
+}
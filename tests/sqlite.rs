@@ -0,0 +1,162 @@
+// Tests for the normalized SQLite export
+
+mod common;
+
+use pretty_assertions::assert_eq;
+use rusqlite::Connection;
+use std::path::Path;
+use vivatech::{partners, sessions, speakers, sqlite};
+
+fn count(conn: &Connection, table: &str) -> i64 {
+    conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+        row.get(0)
+    })
+    .unwrap()
+}
+
+fn speaker_tags(conn: &Connection, speaker_id: &str) -> Vec<String> {
+    let mut statement = conn
+        .prepare(
+            "SELECT tags.name FROM speaker_tags
+             JOIN tags ON tags.id = speaker_tags.tag_id
+             WHERE speaker_tags.speaker_id = ?1
+             ORDER BY tags.name",
+        )
+        .unwrap();
+    statement
+        .query_map([speaker_id], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+fn write_fixture_speakers(path: &Path) -> Vec<speakers::Speaker> {
+    let speakers =
+        speakers::extract_speakers_from_html(&common::fixture("speakers_flight.html")).unwrap();
+    sqlite::write_speakers(&speakers, path).unwrap();
+    speakers
+}
+
+#[test]
+fn writes_speakers_into_normalized_tables() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vivatech.sqlite");
+    write_fixture_speakers(&path);
+
+    let conn = Connection::open(&path).unwrap();
+    assert_eq!(count(&conn, "speakers"), 3);
+    // Zoë has no image
+    assert_eq!(count(&conn, "images"), 2);
+    assert_eq!(
+        speaker_tags(&conn, "55208d04-5e09-f011-aaa7-6045bd9d3cdc"),
+        vec![
+            "Artificial Intelligence",
+            "Climate, Energy & Sustainability"
+        ]
+    );
+    let (is_partner, manager): (bool, Option<String>) = conn
+        .query_row(
+            "SELECT is_partner, communication_manager FROM speakers WHERE firstname = 'Zoë'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert!(is_partner);
+    assert_eq!(manager.as_deref(), Some("press@fusee.example"));
+}
+
+#[test]
+fn repeated_runs_upsert_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vivatech.sqlite");
+    let mut speakers = write_fixture_speakers(&path);
+
+    // A later run sees a new job title and a shorter tag list
+    speakers[1].job_title = "CEO".to_string();
+    speakers[1].tags.truncate(1);
+    sqlite::write_speakers(&speakers, &path).unwrap();
+
+    let conn = Connection::open(&path).unwrap();
+    assert_eq!(count(&conn, "speakers"), 3);
+    assert_eq!(count(&conn, "speaker_tags"), 2);
+    let job_title: String = conn
+        .query_row(
+            "SELECT job_title FROM speakers WHERE id = ?1",
+            [&speakers[1].id],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(job_title, "CEO");
+    assert_eq!(
+        speaker_tags(&conn, &speakers[1].id),
+        vec!["Artificial Intelligence"]
+    );
+}
+
+#[test]
+fn shares_one_database_across_targets() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vivatech.sqlite");
+    write_fixture_speakers(&path);
+
+    let partners =
+        partners::extract_partners_from_html(&common::fixture("partners_next_data.html")).unwrap();
+    sqlite::write_partners(&partners, &path).unwrap();
+    let sessions =
+        sessions::extract_sessions_from_html(&common::fixture("sessions_flight.html")).unwrap();
+    sqlite::write_sessions(&sessions, &path).unwrap();
+
+    let conn = Connection::open(&path).unwrap();
+    assert_eq!(count(&conn, "partners"), 11);
+    assert_eq!(count(&conn, "sessions") as usize, sessions.len());
+    // Session speakers join back to the speakers table
+    let joined: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM session_speakers
+             JOIN speakers ON speakers.id = session_speakers.speaker_id",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!(joined > 0);
+}
+
+#[test]
+fn partner_gaining_an_id_keeps_its_row() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vivatech.sqlite");
+    let mut partners =
+        partners::extract_partners_from_html(&common::fixture("partners_next_data.html")).unwrap();
+    let id = partners[0].id.take();
+
+    // ID-less partners are matched by name from one run to the next
+    sqlite::write_partners(&partners, &path).unwrap();
+    sqlite::write_partners(&partners, &path).unwrap();
+    let conn = Connection::open(&path).unwrap();
+    assert_eq!(count(&conn, "partners"), 11);
+    let (missing, key): (i64, i64) = conn
+        .query_row(
+            "SELECT COUNT(*), MAX(partner_key) FROM partners WHERE id IS NULL",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(missing, 1);
+
+    partners[0].id = id;
+    sqlite::write_partners(&partners, &path).unwrap();
+    assert_eq!(count(&conn, "partners"), 11);
+    let (name, new_key): (String, i64) = conn
+        .query_row(
+            "SELECT name, partner_key FROM partners WHERE id = 'p-0001'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(name, ".lumen");
+    assert_eq!(new_key, key);
+    assert_eq!(
+        sqlite::read_partners(&path).unwrap()[0].id.as_deref(),
+        Some("p-0001")
+    );
+}
@@ -0,0 +1,141 @@
+// Tests for the append-only snapshot store
+
+mod common;
+
+use pretty_assertions::assert_eq;
+use vivatech::diff::{Entity, FieldChange};
+use vivatech::store::{RecordChange, SnapshotStore, LATEST};
+use vivatech::{partners, speakers};
+
+const JENSEN: &str = "9e3a8589-c629-f011-8b3d-6045bd903b46";
+
+fn json(value: impl serde::Serialize) -> serde_json::Value {
+    serde_json::to_value(value).unwrap()
+}
+
+fn fixture_speakers() -> Vec<speakers::Speaker> {
+    speakers::extract_speakers_from_html(&common::fixture("speakers_flight.html")).unwrap()
+}
+
+#[test]
+fn records_and_finds_snapshots() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SnapshotStore::open(dir.path()).unwrap();
+    assert!(store.list().unwrap().is_empty());
+
+    let speakers = fixture_speakers();
+    let first = store
+        .record(Entity::Speakers, "https://vivatech.com/speakers", &speakers)
+        .unwrap();
+    let partners =
+        partners::extract_partners_from_html(&common::fixture("partners_next_data.html")).unwrap();
+    let second = store
+        .record(Entity::Partners, "partners.html", &partners)
+        .unwrap();
+
+    assert_eq!(first.records, 3);
+    assert_eq!(first.content_hash.len(), 64);
+    assert!(first.id.starts_with("speakers-"));
+    assert_eq!(store.list().unwrap(), vec![first.clone(), second.clone()]);
+
+    assert_eq!(store.find(LATEST).unwrap(), second);
+    assert_eq!(store.find("speakers").unwrap(), first);
+    assert!(store.find("nope").is_err());
+
+    // Records come back exactly as they were scraped
+    assert_eq!(json(store.load_speakers(&first).unwrap()), json(speakers));
+    assert_eq!(json(store.load_partners(&second).unwrap()), json(partners));
+    assert!(store.load_partners(&first).is_err());
+
+    // Reopening the store sees the same index
+    let reopened = SnapshotStore::open(dir.path()).unwrap();
+    assert_eq!(reopened.list().unwrap().len(), 2);
+}
+
+#[test]
+fn does_not_store_identical_snapshots_again() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SnapshotStore::open(dir.path()).unwrap();
+
+    let speakers = fixture_speakers();
+    let first = store.record(Entity::Speakers, "a", &speakers).unwrap();
+    let again = store.record(Entity::Speakers, "b", &speakers).unwrap();
+    assert_eq!(again, first);
+    assert_eq!(store.list().unwrap(), vec![first.clone()]);
+    assert_eq!(
+        std::fs::read_dir(dir.path().join("snapshots"))
+            .unwrap()
+            .count(),
+        1
+    );
+
+    // Only the latest snapshot counts: going back to older records is a change
+    let fewer = store.record(Entity::Speakers, "c", &speakers[..2]).unwrap();
+    let back = store.record(Entity::Speakers, "d", &speakers).unwrap();
+    assert_ne!(back.id, first.id);
+    assert_eq!(store.list().unwrap(), vec![first, fewer, back]);
+}
+
+#[test]
+fn tracks_the_history_of_a_record() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SnapshotStore::open(dir.path()).unwrap();
+
+    let mut speakers = fixture_speakers();
+    let first = store.record(Entity::Speakers, "a", &speakers).unwrap();
+    // Unchanged snapshots leave no trace in the history
+    store.record(Entity::Speakers, "b", &speakers[..2]).unwrap();
+    speakers[0].job_title = "CEO".to_string();
+    let third = store.record(Entity::Speakers, "c", &speakers).unwrap();
+    let fourth = store.record(Entity::Speakers, "d", &speakers[1..]).unwrap();
+
+    let history = store.history(JENSEN).unwrap();
+    let changes: Vec<_> = history
+        .iter()
+        .map(|entry| (entry.snapshot.id.as_str(), &entry.change))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (first.id.as_str(), &RecordChange::Added),
+            (
+                third.id.as_str(),
+                &RecordChange::Changed(vec![FieldChange {
+                    field: "JobTitle".to_string(),
+                    old: "Founder and CEO".to_string(),
+                    new: "CEO".to_string(),
+                }])
+            ),
+            (fourth.id.as_str(), &RecordChange::Removed),
+        ]
+    );
+    assert_eq!(history[0].label, "Jensen Huang");
+    assert!(store.history("unknown").unwrap().is_empty());
+}
+
+#[test]
+fn gives_snapshots_of_the_same_millisecond_their_own_ids() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SnapshotStore::open(dir.path()).unwrap();
+
+    let speakers = fixture_speakers();
+    let snapshots: Vec<_> = (0..10)
+        .map(|round| {
+            let records = if round % 2 == 0 {
+                &speakers[..]
+            } else {
+                &speakers[..1]
+            };
+            store.record(Entity::Speakers, "a", records).unwrap()
+        })
+        .collect();
+
+    assert_eq!(store.list().unwrap(), snapshots);
+    for snapshot in &snapshots {
+        assert_eq!(store.find(&snapshot.id).unwrap(), *snapshot);
+        assert_eq!(
+            store.load_speakers(snapshot).unwrap().len(),
+            snapshot.records
+        );
+    }
+}
@@ -0,0 +1,231 @@
+// Tests for change notification webhooks
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::server::{MockResponse, MockServer};
+use pretty_assertions::assert_eq;
+use std::time::Duration;
+use vivatech::diff::{ChangedRecord, Entity, EntityDiff, FieldChange, RecordRef};
+use vivatech::fetch::RetryPolicy;
+use vivatech::store::SnapshotInfo;
+use vivatech::webhook::{self, ChangeEvent, Notifier, Webhook, WebhookKind};
+
+fn snapshot(id: &str) -> SnapshotInfo {
+    SnapshotInfo {
+        id: id.to_string(),
+        entity: Entity::Speakers,
+        taken_at: Utc.with_ymd_and_hms(2025, 6, 11, 9, 0, 0).unwrap(),
+        source: "https://vivatechnology.com/speakers".to_string(),
+        content_hash: "0".repeat(64),
+        records: 2,
+    }
+}
+
+fn speakers_diff() -> EntityDiff {
+    EntityDiff {
+        entity: Entity::Speakers,
+        added: vec![RecordRef {
+            id: "1".to_string(),
+            label: "Jensen Huang".to_string(),
+        }],
+        removed: Vec::new(),
+        changed: vec![ChangedRecord {
+            id: "2".to_string(),
+            label: "Zoë Dupré".to_string(),
+            changes: vec![FieldChange {
+                field: "JobTitle".to_string(),
+                old: "CTO".to_string(),
+                new: "CEO".to_string(),
+            }],
+        }],
+    }
+}
+
+#[test]
+fn renders_templates_and_payloads() {
+    let (previous, current, diff) = (snapshot("old"), snapshot("new"), speakers_diff());
+    let event = ChangeEvent {
+        previous: &previous,
+        snapshot: &current,
+        diff: &diff,
+    };
+    assert!(event.is_notable());
+
+    let message = webhook::render_message(webhook::DEFAULT_TEMPLATE, &event, WebhookKind::Slack);
+    assert_eq!(
+        message,
+        "VivaTech speakers: 1 new, 1 updated, 0 removed\n\
+         • New: Jensen Huang\n\
+         • Updated: Zoë Dupré (JobTitle)"
+    );
+    assert_eq!(
+        webhook::render_message(
+            "{records} in {snapshot} from {source} {unknown}",
+            &event,
+            WebhookKind::Generic
+        ),
+        "2 in new from https://vivatechnology.com/speakers {unknown}"
+    );
+
+    // Scraped text cannot ping a Slack channel, but the template keeps its markup
+    let diff_with_markup = EntityDiff {
+        added: vec![RecordRef {
+            id: "3".to_string(),
+            label: "<!channel> & Co".to_string(),
+        }],
+        ..diff.clone()
+    };
+    let markup_event = ChangeEvent {
+        diff: &diff_with_markup,
+        ..event
+    };
+    assert_eq!(
+        webhook::render_message("<!here> {changes}", &markup_event, WebhookKind::Slack),
+        "<!here> • New: &lt;!channel&gt; &amp; Co\n• Updated: Zoë Dupré (JobTitle)"
+    );
+    assert_eq!(
+        webhook::render_message("{changes}", &markup_event, WebhookKind::Generic),
+        "• New: <!channel> & Co\n• Updated: Zoë Dupré (JobTitle)"
+    );
+
+    let slack: serde_json::Value =
+        serde_json::from_slice(&webhook::payload(WebhookKind::Slack, &event, "hi").unwrap())
+            .unwrap();
+    assert_eq!(slack, serde_json::json!({ "text": "hi" }));
+
+    let generic: serde_json::Value =
+        serde_json::from_slice(&webhook::payload(WebhookKind::Generic, &event, "hi").unwrap())
+            .unwrap();
+    assert_eq!(generic["event"], "vivatech.changes");
+    assert_eq!(generic["entity"], "speakers");
+    assert_eq!(generic["message"], "hi");
+    assert_eq!(generic["previous_snapshot"]["id"], "old");
+    assert_eq!(generic["added"][0]["label"], "Jensen Huang");
+    assert_eq!(generic["changed"][0]["changes"][0]["new"], "CEO");
+
+    // Removals alone do not trigger a notification
+    let removed_only = EntityDiff {
+        added: Vec::new(),
+        changed: Vec::new(),
+        removed: diff.added.clone(),
+        ..diff
+    };
+    let event = ChangeEvent {
+        diff: &removed_only,
+        ..event
+    };
+    assert!(!event.is_notable());
+}
+
+#[test]
+fn signs_bodies_with_hmac_sha256() {
+    assert_eq!(
+        webhook::sign("key", b"The quick brown fox jumps over the lazy dog"),
+        "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+    );
+}
+
+#[test]
+fn retries_rate_limited_and_failed_deliveries() {
+    let server = MockServer::start();
+    server
+        .route(
+            "/slack",
+            vec![
+                MockResponse::status(429).header("Retry-After", "0"),
+                MockResponse::ok(String::new()),
+            ],
+        )
+        .route(
+            "/hook",
+            vec![MockResponse::status(503), MockResponse::ok(String::new())],
+        )
+        .route("/gone", vec![MockResponse::status(404)]);
+    let webhooks = |paths: &[(&str, WebhookKind)]| -> Vec<Webhook> {
+        paths
+            .iter()
+            .map(|&(path, kind)| Webhook {
+                url: server.url(path),
+                kind,
+            })
+            .collect()
+    };
+    let policy = RetryPolicy {
+        base_delay: Duration::from_millis(10),
+        ..RetryPolicy::default()
+    };
+    let (previous, current, diff) = (snapshot("old"), snapshot("new"), speakers_diff());
+    let event = ChangeEvent {
+        previous: &previous,
+        snapshot: &current,
+        diff: &diff,
+    };
+
+    let notifier = Notifier::new(
+        webhooks(&[
+            ("/slack", WebhookKind::Slack),
+            ("/hook", WebhookKind::Generic),
+        ]),
+        Duration::from_secs(5),
+    )
+    .unwrap()
+    .with_retry_policy(policy.clone());
+    assert!(notifier.notify(&event).unwrap());
+
+    for path in ["/slack", "/hook"] {
+        let requests = server.requests_to(path);
+        assert_eq!(requests.len(), 2, "{path}");
+        // Deliveries say who sends them rather than posing as a browser
+        assert_eq!(requests[1].header("User-Agent"), Some(webhook::USER_AGENT));
+        assert!(webhook::USER_AGENT.starts_with("vivatech-scraper/"));
+    }
+
+    // Client errors are not retried
+    let notifier = Notifier::new(
+        webhooks(&[("/gone", WebhookKind::Generic)]),
+        Duration::from_secs(5),
+    )
+    .unwrap()
+    .with_retry_policy(policy);
+    let error = notifier.notify(&event).unwrap_err();
+    assert!(format!("{error:#}").contains("404"), "{error:#}");
+    assert_eq!(server.requests_to("/gone").len(), 1);
+}
+
+#[test]
+fn does_not_retry_timed_out_deliveries_or_log_their_url() {
+    let server = MockServer::start();
+    server.route(
+        "/services/T000/B000/secret-token",
+        vec![MockResponse::ok(String::new()).delay(Duration::from_millis(500))],
+    );
+    let (previous, current, diff) = (snapshot("old"), snapshot("new"), speakers_diff());
+    let event = ChangeEvent {
+        previous: &previous,
+        snapshot: &current,
+        diff: &diff,
+    };
+
+    let notifier = Notifier::new(
+        vec![Webhook {
+            url: server.url("/services/T000/B000/secret-token"),
+            kind: WebhookKind::Slack,
+        }],
+        Duration::from_millis(100),
+    )
+    .unwrap()
+    .with_retry_policy(RetryPolicy {
+        base_delay: Duration::from_millis(10),
+        ..RetryPolicy::default()
+    });
+    let error = format!("{:#}", notifier.notify(&event).unwrap_err());
+
+    // The server may have received the message, so a retry could post it twice
+    assert_eq!(
+        server.requests_to("/services/T000/B000/secret-token").len(),
+        1
+    );
+    assert!(error.contains("http://127.0.0.1"), "{error}");
+    assert!(!error.contains("secret-token"), "{error}");
+}
@@ -0,0 +1,74 @@
+// Tests for the Excel workbook export, inspecting the workbook's XML parts
+
+mod common;
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use vivatech::{partners, speakers, xlsx};
+
+fn part(path: &Path, name: &str) -> String {
+    let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+    let mut xml = String::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut xml)
+        .unwrap();
+    xml
+}
+
+#[test]
+fn writes_entity_and_summary_sheets() {
+    let speakers =
+        speakers::extract_speakers_from_html(&common::fixture("speakers_flight.html")).unwrap();
+    let partners =
+        partners::extract_partners_from_html(&common::fixture("partners_next_data.html")).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vivatech.xlsx");
+
+    xlsx::write_workbook(&speakers, &partners, &path).unwrap();
+
+    let workbook = part(&path, "xl/workbook.xml");
+    let sheets: Vec<&str> = ["Speakers", "Partners", "Summary"]
+        .into_iter()
+        .filter(|name| workbook.contains(&format!("name=\"{name}\"")))
+        .collect();
+    assert_eq!(sheets, vec!["Speakers", "Partners", "Summary"]);
+
+    // Frozen header and autofilter over every speaker row
+    let speaker_sheet = part(&path, "xl/worksheets/sheet1.xml");
+    assert!(speaker_sheet.contains("state=\"frozen\""));
+    assert!(speaker_sheet.contains("<autoFilter ref=\"A1:R4\""));
+    // Image URLs and websites are hyperlinks
+    assert!(part(&path, "xl/worksheets/_rels/sheet1.xml.rels").contains("/only-main"));
+    assert!(part(&path, "xl/worksheets/_rels/sheet2.xml.rels")
+        .contains("Target=\"https://www.dotlumen.com\""));
+
+    // Partner countries are counted on the summary sheet
+    let strings = part(&path, "xl/sharedStrings.xml");
+    for label in [
+        "Company",
+        "Tag",
+        "Country",
+        "Tencent/AI \\ Lab",
+        "Switzerland",
+    ] {
+        assert!(strings.contains(label), "missing {label}");
+    }
+}
+
+#[test]
+fn leaves_out_sheets_without_data() {
+    let speakers =
+        speakers::extract_speakers_from_html(&common::fixture("speakers_flight.html")).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("speakers.xlsx");
+
+    xlsx::write_workbook(&speakers, &[], &path).unwrap();
+
+    let workbook = part(&path, "xl/workbook.xml");
+    assert!(workbook.contains("name=\"Speakers\""));
+    assert!(!workbook.contains("name=\"Partners\""));
+    assert!(workbook.contains("name=\"Summary\""));
+}